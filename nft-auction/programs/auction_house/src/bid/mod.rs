@@ -10,6 +10,197 @@ use crate::{
     constants::*, errors::AuctionHouseError, utils::*, AuctionHouse, Auctioneer, TRADE_STATE_SIZE,
 };
 
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct Buy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<Account<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct PublicBuy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<Account<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA, not tied to the current holder's token account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
 #[derive(Accounts)]
 #[instruction(
     trade_state_bump: u8,
@@ -119,6 +310,196 @@ pub struct AuctioneerBuy<'info> {
     rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct AuctioneerPublicBuy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<Account<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// CHECK: Verified in auctioneer seeds check.
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    auctioneer_authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA, not tied to the current holder's token account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Create a private buy bid by creating a `buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+pub fn private_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    if ctx.accounts.auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        false,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
+/// Create a public buy bid, matchable against whichever wallet holds the token, by creating a `buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+pub fn public_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, PublicBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    if ctx.accounts.auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        true,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
 pub fn auctioneer_private_bid<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
     trade_state_bump: u8,
@@ -155,6 +536,43 @@ pub fn auctioneer_private_bid<'info>(
     )
 }
 
+/// Create a public buy bid through the auctioneer, matchable against whichever wallet holds the token.
+pub fn auctioneer_public_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    auctioneer_bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        true,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn auctioneer_bid_logic<'info>(
     wallet: Signer<'info>,
@@ -183,6 +601,56 @@ pub fn auctioneer_bid_logic<'info>(
         return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
     }
 
+    bid_logic(
+        wallet,
+        payment_account,
+        transfer_authority,
+        treasury_mint,
+        token_account,
+        metadata,
+        escrow_payment_account,
+        auction_house,
+        auction_house_fee_account,
+        buyer_trade_state,
+        authority,
+        token_program,
+        system_program,
+        rent,
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        public,
+        escrow_canonical_bump,
+        trade_state_canonical_bump,
+    )
+}
+
+/// Validate the bid, fund the buyer escrow up to `buyer_price` and create the buyer trade state.
+#[allow(clippy::too_many_arguments)]
+pub fn bid_logic<'info>(
+    wallet: Signer<'info>,
+    payment_account: UncheckedAccount<'info>,
+    transfer_authority: UncheckedAccount<'info>,
+    treasury_mint: Account<'info, Mint>,
+    token_account: Account<'info, TokenAccount>,
+    metadata: UncheckedAccount<'info>,
+    escrow_payment_account: UncheckedAccount<'info>,
+    auction_house: &mut Box<Account<'info, AuctionHouse>>,
+    auction_house_fee_account: UncheckedAccount<'info>,
+    buyer_trade_state: UncheckedAccount<'info>,
+    authority: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    public: bool,
+    escrow_canonical_bump: u8,
+    trade_state_canonical_bump: u8,
+) -> Result<()> {
     if (escrow_canonical_bump != escrow_payment_bump)
         || (trade_state_canonical_bump != trade_state_bump)
     {
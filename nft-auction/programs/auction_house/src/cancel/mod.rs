@@ -1,8 +1,77 @@
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
 use anchor_lang::solana_program::program_memory::sol_memset;
 use anchor_lang::{prelude::*, solana_program::program::invoke, AnchorDeserialize};
+use mpl_token_metadata::instruction::{builders::RevokeBuilder, InstructionBuilder, RevokeArgs};
 use spl_token::instruction::revoke;
 
+#[derive(Accounts, Clone)]
+#[instruction(buyer_price: u64, token_size: u64)]
+pub struct Cancel<'info> {
+    /// CHECK: Wallet validated as owner in cancel logic.
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// SPL token account containing the token of the sale to be canceled.
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Token mint account of SPL token.
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Validated as a signer in cancel_logic.
+    /// Auction House instance authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in cancel_logic.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in cancel_logic.
+    /// Trade state PDA account representing the bid or ask to be canceled.
+    #[account(mut)]
+    pub trade_state: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> From<AuctioneerCancel<'info>> for Cancel<'info> {
+    fn from(a: AuctioneerCancel<'info>) -> Cancel<'info> {
+        Cancel {
+            wallet: a.wallet,
+            token_account: a.token_account,
+            token_mint: a.token_mint,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            trade_state: a.trade_state,
+            token_program: a.token_program,
+        }
+    }
+}
+
 #[derive(Accounts, Clone)]
 #[instruction(buyer_price: u64, token_size: u64)]
 pub struct AuctioneerCancel<'info> {
@@ -72,6 +141,28 @@ pub struct AuctioneerCancel<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Cancel a bid or ask by revoking the token delegate, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
+pub fn cancel<'info>(
+    ctx: Context<'_, '_, '_, 'info, Cancel<'info>>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let mut accounts: Cancel<'info> = (*ctx.accounts).clone();
+
+    cancel_logic(
+        &mut accounts,
+        ctx.remaining_accounts,
+        buyer_price,
+        token_size,
+    )
+}
+
 pub fn auctioneer_cancel<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerCancel<'info>>,
     buyer_price: u64,
@@ -83,15 +174,21 @@ pub fn auctioneer_cancel<'info>(
         return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
     }
 
-    let mut accounts: AuctioneerCancel<'info> = (*ctx.accounts).clone();
+    let mut accounts: Cancel<'info> = (*ctx.accounts).clone().into();
 
-    cancel_logic(&mut accounts, buyer_price, token_size)
+    cancel_logic(
+        &mut accounts,
+        ctx.remaining_accounts,
+        buyer_price,
+        token_size,
+    )
 }
 
 #[allow(clippy::needless_lifetimes)]
 #[allow(clippy::extra_unused_lifetimes)]
 fn cancel_logic<'c, 'info>(
-    accounts: &mut AuctioneerCancel<'info>,
+    accounts: &mut Cancel<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
     buyer_price: u64,
     token_size: u64,
 ) -> Result<()> {
@@ -136,20 +233,103 @@ fn cancel_logic<'c, 'info>(
     )?;
 
     if token_account.owner == wallet.key() && wallet.is_signer {
-        invoke(
-            &revoke(
-                &token_program.key(),
-                &token_account.key(),
-                &wallet.key(),
-                &[],
-            )
-            .unwrap(),
-            &[
-                token_program.to_account_info(),
-                token_account.to_account_info(),
-                wallet.to_account_info(),
-            ],
-        )?;
+        // Programmable NFT listings pass the token metadata accounts needed to revoke the sale
+        // delegate: token metadata program, metadata, program as signer, master edition, token
+        // record, authorization rules program, authorization rules, system program and the
+        // instructions sysvar. Token metadata keeps programmable NFT token accounts frozen, so
+        // their listings can't be cancelled without these accounts.
+        let programmable = match remaining_accounts.get(1) {
+            Some(metadata) => {
+                assert_derivation(
+                    &mpl_token_metadata::id(),
+                    metadata,
+                    &[
+                        mpl_token_metadata::state::PREFIX.as_bytes(),
+                        mpl_token_metadata::id().as_ref(),
+                        token_mint.key().as_ref(),
+                    ],
+                )?;
+                is_programmable(metadata)?
+            }
+            None if token_account.is_frozen() => {
+                return Err(AuctionHouseError::MissingProgrammableAccounts.into());
+            }
+            None => false,
+        };
+
+        if programmable {
+            if remaining_accounts.len() < 9 {
+                return Err(AuctionHouseError::MissingProgrammableAccounts.into());
+            }
+
+            let remaining_accounts = &mut remaining_accounts.iter();
+            let token_metadata_program = next_account_info(remaining_accounts)?;
+            let metadata = next_account_info(remaining_accounts)?;
+            let program_as_signer = next_account_info(remaining_accounts)?;
+            let edition = next_account_info(remaining_accounts)?;
+            let token_record = next_account_info(remaining_accounts)?;
+            let auth_rules_program = next_account_info(remaining_accounts)?;
+            let auth_rules = next_account_info(remaining_accounts)?;
+            let system_program = next_account_info(remaining_accounts)?;
+            let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+            assert_keys_equal(token_metadata_program.key(), mpl_token_metadata::id())?;
+
+            let mut revoke_builder = RevokeBuilder::new();
+            revoke_builder
+                .delegate(program_as_signer.key())
+                .metadata(metadata.key())
+                .master_edition(edition.key())
+                .token_record(token_record.key())
+                .mint(token_mint.key())
+                .token(token_account.key())
+                .authority(wallet.key())
+                .payer(wallet.key())
+                .system_program(system_program.key())
+                .sysvar_instructions(sysvar_instructions.key())
+                .spl_token_program(token_program.key())
+                .authorization_rules_program(auth_rules_program.key())
+                .authorization_rules(auth_rules.key());
+
+            let revoke_ix = revoke_builder
+                .build(RevokeArgs::SaleV1)
+                .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?
+                .instruction();
+
+            invoke(
+                &revoke_ix,
+                &[
+                    token_metadata_program.clone(),
+                    program_as_signer.clone(),
+                    metadata.clone(),
+                    edition.clone(),
+                    token_record.clone(),
+                    token_mint.to_account_info(),
+                    token_account.to_account_info(),
+                    wallet.to_account_info(),
+                    system_program.clone(),
+                    sysvar_instructions.clone(),
+                    token_program.to_account_info(),
+                    auth_rules_program.clone(),
+                    auth_rules.clone(),
+                ],
+            )?;
+        } else {
+            invoke(
+                &revoke(
+                    &token_program.key(),
+                    &token_account.key(),
+                    &wallet.key(),
+                    &[],
+                )
+                .unwrap(),
+                &[
+                    token_program.to_account_info(),
+                    token_account.to_account_info(),
+                    wallet.to_account_info(),
+                ],
+            )?;
+        }
     }
 
     let curr_lamp = trade_state.lamports();
@@ -3,6 +3,9 @@ pub const FEE_PAYER: &str = "fee_payer";
 pub const TREASURY: &str = "treasury";
 pub const SIGNER: &str = "signer";
 pub const AUCTIONEER: &str = "auctioneer";
+pub const LISTING_RECEIPT_PREFIX: &str = "listing_receipt";
+pub const BID_RECEIPT_PREFIX: &str = "bid_receipt";
+pub const PURCHASE_RECEIPT_PREFIX: &str = "purchase_receipt";
 pub const TRADE_STATE_SIZE: usize = 1;
 pub const AUCTIONEER_SIZE: usize = 8 +                      // Anchor discriminator/sighash
 32 +                                                        // Auctioneer authority
@@ -29,3 +32,46 @@ pub const AUCTION_HOUSE_SIZE: usize = 8 +                   // key
 32 +                                                         // auctioneer address
 172                                                         // padding
 ;
+
+pub const LISTING_RECEIPT_SIZE: usize = 8 +                 // Anchor discriminator/sighash
+32 +                                                        // trade state
+32 +                                                        // bookkeeper
+32 +                                                        // auction house
+32 +                                                        // seller
+32 +                                                        // metadata
+1 + 32 +                                                    // purchase receipt
+8 +                                                         // price
+8 +                                                         // token size
+1 +                                                         // bump
+1 +                                                         // trade state bump
+8 +                                                         // created at
+1 + 8                                                       // canceled at
+;
+
+pub const BID_RECEIPT_SIZE: usize = 8 +                     // Anchor discriminator/sighash
+32 +                                                        // trade state
+32 +                                                        // bookkeeper
+32 +                                                        // auction house
+32 +                                                        // buyer
+32 +                                                        // metadata
+1 + 32 +                                                    // token account
+1 + 32 +                                                    // purchase receipt
+8 +                                                         // price
+8 +                                                         // token size
+1 +                                                         // bump
+1 +                                                         // trade state bump
+8 +                                                         // created at
+1 + 8                                                       // canceled at
+;
+
+pub const PURCHASE_RECEIPT_SIZE: usize = 8 +                // Anchor discriminator/sighash
+32 +                                                        // bookkeeper
+32 +                                                        // buyer
+32 +                                                        // seller
+32 +                                                        // auction house
+32 +                                                        // metadata
+8 +                                                         // token size
+8 +                                                         // price
+1 +                                                         // bump
+8                                                           // created at
+;
@@ -89,4 +89,32 @@ pub enum AuctionHouseError {
     // 6021
     #[msg("Insufficient funds in escrow account.")]
     InsufficientFunds,
+
+    // 6022
+    #[msg("Auction House has an Auctioneer program set, use the auctioneer instructions.")]
+    MustUseAuctioneerHandler,
+
+    // 6023
+    #[msg("Partial order price per token does not match the listing price per token.")]
+    PartialPriceMismatch,
+
+    // 6024
+    #[msg("Both partial order size and partial order price are required for a partial order.")]
+    MissingElementForPartialOrder,
+
+    // 6025
+    #[msg("The previous instruction does not match the receipt being printed.")]
+    InstructionMismatch,
+
+    // 6026
+    #[msg("Failed to build the token metadata instruction.")]
+    InstructionBuilderFailed,
+
+    // 6027
+    #[msg("Metadata account must be writable to handle a programmable NFT.")]
+    MetadataNotWritable,
+
+    // 6028
+    #[msg("Token metadata accounts are required to handle a programmable NFT.")]
+    MissingProgrammableAccounts,
 }
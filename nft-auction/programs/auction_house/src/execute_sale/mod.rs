@@ -1,7 +1,214 @@
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, Auctioneer, *};
 use anchor_lang::{prelude::*, solana_program::program_pack::Pack, AnchorDeserialize};
+use mpl_token_metadata::instruction::{
+    builders::TransferBuilder, InstructionBuilder, TransferArgs,
+};
 use spl_token::state::Account as SplAccount;
 
+#[derive(Accounts)]
+#[instruction(
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct ExecuteSale<'info> {
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer user wallet account.
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller user wallet account.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    ///Token account where the SPL token is stored.
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Token mint account for the SPL token.
+    pub token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Metaplex metadata account decorating SPL mint account.
+    /// Writable so token metadata can update programmable NFTs.
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Auction House instance authority.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance treasury account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            TREASURY.as_bytes()
+        ],
+        bump=auction_house.treasury_bump
+    )]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer trade state PDA account encoding the buy order, either private (seeded with the seller token account) or public (seeded with the mint only).
+    #[account(mut)]
+    pub buyer_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Seller trade state PDA account encoding the sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_mint.key().as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump=seller_trade_state.to_account_info().data.borrow()[0]
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Free seller trade state PDA account encoding a free sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_mint.key().as_ref(),
+            &0u64.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub free_trade_state: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(
+        seeds=[
+            PREFIX.as_bytes(), SIGNER.as_bytes()
+        ],
+        bump
+    )]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    // Remaining accounts (Provide only if token metadata has creators)
+    //    #[account(mut)]
+    //    pub creator1: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator2: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator3: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator4: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator5: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> From<&AuctioneerExecuteSale<'info>> for ExecuteSale<'info> {
+    fn from(a: &AuctioneerExecuteSale<'info>) -> ExecuteSale<'info> {
+        ExecuteSale {
+            buyer: a.buyer.clone(),
+            seller: a.seller.clone(),
+            token_account: a.token_account.clone(),
+            token_mint: a.token_mint.clone(),
+            metadata: a.metadata.clone(),
+            treasury_mint: a.treasury_mint.clone(),
+            escrow_payment_account: a.escrow_payment_account.clone(),
+            seller_payment_receipt_account: a.seller_payment_receipt_account.clone(),
+            buyer_receipt_token_account: a.buyer_receipt_token_account.clone(),
+            authority: a.authority.clone(),
+            auction_house: a.auction_house.clone(),
+            auction_house_fee_account: a.auction_house_fee_account.clone(),
+            auction_house_treasury: a.auction_house_treasury.clone(),
+            buyer_trade_state: a.buyer_trade_state.clone(),
+            seller_trade_state: a.seller_trade_state.clone(),
+            free_trade_state: a.free_trade_state.clone(),
+            token_program: a.token_program.clone(),
+            system_program: a.system_program.clone(),
+            associated_token_program: a.associated_token_program.clone(),
+            program_as_signer: a.program_as_signer.clone(),
+            rent: a.rent.clone(),
+        }
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(
     escrow_payment_bump: u8,
@@ -33,6 +240,7 @@ pub struct AuctioneerExecuteSale<'info> {
 
     /// CHECK: Validated in execute_sale_logic.
     /// Metaplex metadata account decorating SPL mint account.
+    /// Read-only so existing auctioneer programs keep working, programmable NFTs need it writable.
     pub metadata: UncheckedAccount<'info>,
 
     /// CHECK: Validated in execute_sale_logic.
@@ -113,7 +321,7 @@ pub struct AuctioneerExecuteSale<'info> {
     pub auction_house_treasury: UncheckedAccount<'info>,
 
     /// CHECK: Validated in execute_sale_logic.
-    /// Buyer trade state PDA account encoding the buy order.
+    /// Buyer trade state PDA account encoding the buy order, either private (seeded with the seller token account) or public (seeded with the mint only).
     #[account(mut)]
     pub buyer_trade_state: UncheckedAccount<'info>,
 
@@ -192,6 +400,77 @@ pub struct AuctioneerExecuteSale<'info> {
     //    pub creator5: Option<UncheckedAccount<'info>>,
 }
 
+/// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
+pub fn execute_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    execute_partial_sale(
+        ctx,
+        escrow_payment_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        None,
+        None,
+    )
+}
+
+/// Execute a partial sale of `partial_order_size` tokens at `partial_order_price` out of a multi-unit listing, keeping the seller trade state open while tokens remain delegated.
+pub fn execute_partial_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let escrow_canonical_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let free_trade_state_canonical_bump = *ctx
+        .bumps
+        .get("free_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_canonical_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (escrow_canonical_bump != escrow_payment_bump)
+        || (free_trade_state_canonical_bump != free_trade_state_bump)
+        || (program_as_signer_canonical_bump != program_as_signer_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    execute_sale_logic(
+        ctx.accounts,
+        ctx.remaining_accounts,
+        escrow_payment_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    )
+}
+
 pub fn auctioneer_execute_sale<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerExecuteSale<'info>>,
     escrow_payment_bump: u8,
@@ -226,27 +505,33 @@ pub fn auctioneer_execute_sale<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    auctioneer_execute_sale_logic(
-        ctx.accounts,
+    let mut accounts: ExecuteSale<'info> = (&*ctx.accounts).into();
+
+    execute_sale_logic(
+        &mut accounts,
         ctx.remaining_accounts,
         escrow_payment_bump,
         free_trade_state_bump,
         program_as_signer_bump,
         buyer_price,
         token_size,
+        None,
+        None,
     )
 }
 
 /// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
 #[inline(never)]
-fn auctioneer_execute_sale_logic<'c, 'info>(
-    accounts: &mut AuctioneerExecuteSale<'info>,
+fn execute_sale_logic<'c, 'info>(
+    accounts: &mut ExecuteSale<'info>,
     remaining_accounts: &'c [AccountInfo<'info>],
     escrow_payment_bump: u8,
     _free_trade_state_bump: u8,
     program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
 ) -> Result<()> {
     let buyer = &accounts.buyer;
     let seller = &accounts.seller;
@@ -284,7 +569,31 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
 
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
-    if buyer_price == 0 && !authority_clone.is_signer && !seller.is_signer {
+    // The seller trade state always encodes the full listing, while the buyer trade state and
+    // every transfer below use the size and price of the order actually being filled.
+    let (price, size) = match (partial_order_size, partial_order_price) {
+        (Some(partial_size), Some(partial_price)) => {
+            if partial_size == 0 {
+                return Err(AuctionHouseError::InvalidTokenAmount.into());
+            }
+            if partial_size > token_size {
+                return Err(AuctionHouseError::NotEnoughTokensAvailableForPurchase.into());
+            }
+
+            // Compare per-token prices without division, so remainders can't be dropped
+            if (partial_price as u128) * (token_size as u128)
+                != (buyer_price as u128) * (partial_size as u128)
+            {
+                return Err(AuctionHouseError::PartialPriceMismatch.into());
+            }
+
+            (partial_price, partial_size)
+        }
+        (None, None) => (buyer_price, token_size),
+        _ => return Err(AuctionHouseError::MissingElementForPartialOrder.into()),
+    };
+
+    if price == 0 && !authority_clone.is_signer && !seller.is_signer {
         return Err(
             AuctionHouseError::CannotMatchFreeSalesWithoutAuctionHouseOrSellerSignoff.into(),
         );
@@ -316,18 +625,20 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
 
     let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
 
+    // Accepts a private bid on this token account as well as a public bid on the mint,
+    // which can be matched against whichever wallet currently holds the token.
     assert_valid_trade_state(
         &buyer.key(),
         auction_house,
-        buyer_price,
-        token_size,
+        price,
+        size,
         buyer_trade_state,
         &token_mint.key(),
         &token_account.key(),
         ts_bump,
     )?;
 
-    if token_account_data.amount < token_size {
+    if token_account_data.amount < size || token_account_data.delegated_amount < size {
         return Err(AuctionHouseError::NotEnoughTokensAvailableForPurchase.into());
     };
 
@@ -369,8 +680,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
     // rent-exemption checked accounts.  The fee payer makes up the shortfall up to the amount of
     // rent for an empty account.
     if is_native {
-        let rent_shortfall =
-            verify_withdrawal(escrow_payment_account.to_account_info(), buyer_price)?;
+        let rent_shortfall = verify_withdrawal(escrow_payment_account.to_account_info(), price)?;
         if rent_shortfall > 0 {
             invoke_signed(
                 &system_instruction::transfer(
@@ -431,7 +741,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &rent_clone,
         &signer_seeds_for_royalties,
         fee_payer_seeds,
-        buyer_price,
+        price,
         is_native,
     )?;
 
@@ -442,7 +752,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &token_clone,
         &sys_clone,
         &signer_seeds_for_royalties,
-        buyer_price,
+        price,
         is_native,
     )?;
 
@@ -543,23 +853,96 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &[program_as_signer_bump],
     ];
 
-    invoke_signed(
-        &spl_token::instruction::transfer(
-            token_program.key,
-            &token_account.key(),
-            &buyer_receipt_token_account.key(),
-            &program_as_signer.key(),
-            &[],
-            token_size,
-        )?,
-        &[
-            token_account.to_account_info(),
-            buyer_receipt_clone,
-            program_as_signer.to_account_info(),
-            token_clone,
-        ],
-        &[&program_as_signer_seeds],
-    )?;
+    if is_programmable(&metadata_clone)? {
+        // Programmable NFTs are transferred through token metadata so the rule set is enforced.
+        // Remaining accounts after the creators: token metadata program, master edition, seller
+        // token record, buyer token record, authorization rules program, authorization rules
+        // and the instructions sysvar.
+        if !metadata.is_writable {
+            return Err(AuctionHouseError::MetadataNotWritable.into());
+        }
+
+        let token_metadata_program = next_account_info(remaining_accounts)?;
+        let edition = next_account_info(remaining_accounts)?;
+        let owner_token_record = next_account_info(remaining_accounts)?;
+        let destination_token_record = next_account_info(remaining_accounts)?;
+        let auth_rules_program = next_account_info(remaining_accounts)?;
+        let auth_rules = next_account_info(remaining_accounts)?;
+        let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+        assert_keys_equal(token_metadata_program.key(), mpl_token_metadata::id())?;
+
+        let mut transfer_builder = TransferBuilder::new();
+        transfer_builder
+            .token(token_account.key())
+            .token_owner(seller.key())
+            .destination(buyer_receipt_token_account.key())
+            .destination_owner(buyer.key())
+            .mint(token_mint.key())
+            .metadata(metadata.key())
+            .edition(edition.key())
+            .owner_token_record(owner_token_record.key())
+            .destination_token_record(destination_token_record.key())
+            .authority(program_as_signer.key())
+            .payer(fee_payer.key())
+            .system_program(system_program.key())
+            .sysvar_instructions(sysvar_instructions.key())
+            .spl_token_program(token_program.key())
+            .spl_ata_program(associated_token_program.key())
+            .authorization_rules_program(auth_rules_program.key())
+            .authorization_rules(auth_rules.key());
+
+        let transfer_ix = transfer_builder
+            .build(TransferArgs::V1 {
+                amount: size,
+                authorization_data: None,
+            })
+            .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?
+            .instruction();
+
+        invoke_signed(
+            &transfer_ix,
+            &[
+                token_metadata_program.clone(),
+                token_account_clone,
+                seller.to_account_info(),
+                buyer_receipt_clone,
+                buyer.to_account_info(),
+                token_mint.to_account_info(),
+                metadata_clone,
+                edition.clone(),
+                owner_token_record.clone(),
+                destination_token_record.clone(),
+                program_as_signer.to_account_info(),
+                fee_payer_clone,
+                sys_clone,
+                sysvar_instructions.clone(),
+                token_clone,
+                ata_clone,
+                auth_rules_program.clone(),
+                auth_rules.clone(),
+            ],
+            &[&program_as_signer_seeds, fee_payer_seeds],
+        )?;
+    } else {
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &token_account.key(),
+                &buyer_receipt_token_account.key(),
+                &program_as_signer.key(),
+                &[],
+                size,
+            )?,
+            &[
+                token_account.to_account_info(),
+                buyer_receipt_clone,
+                program_as_signer.to_account_info(),
+                token_clone,
+            ],
+            &[&program_as_signer_seeds],
+        )?;
+    }
 
     // Close the buyer trade state account if the rest of execute sale was successful.
     close_account(
@@ -567,8 +950,10 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &fee_payer.to_account_info(),
     )?;
 
-    let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
-    if token_account_data.delegated_amount == 0 {
+    // Token metadata may close the seller token account once a programmable NFT leaves it.
+    if token_account.data_is_empty()
+        || SplAccount::unpack(&token_account.data.borrow())?.delegated_amount == 0
+    {
         close_account(
             &seller_trade_state.to_account_info(),
             &fee_payer.to_account_info(),
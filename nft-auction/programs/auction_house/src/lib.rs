@@ -8,6 +8,7 @@ pub mod deposit;
 pub mod errors;
 pub mod execute_sale;
 pub mod pda;
+pub mod receipt;
 pub mod sell;
 pub mod state;
 pub mod utils;
@@ -17,7 +18,7 @@ pub use state::*;
 
 use crate::{
     auctioneer::*, bid::*, cancel::*, constants::*, deposit::*, errors::AuctionHouseError,
-    execute_sale::*, sell::*, utils::*, withdraw::*,
+    execute_sale::*, receipt::*, sell::*, utils::*, withdraw::*,
 };
 
 use anchor_lang::{
@@ -318,6 +319,108 @@ pub mod auction_house {
         Ok(())
     }
 
+    /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
+    /// Programmable NFTs are delegated through token metadata using the accounts passed as remaining accounts.
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        trade_state_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        sell::sell(
+            ctx,
+            trade_state_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
+    /// Create a private buy bid by creating a `buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        bid::private_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
+    /// Create a public buy bid that can be matched against whichever wallet holds the token.
+    pub fn public_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, PublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        bid::public_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
+    /// Cancel a bid or ask by revoking the token delegate and closing the trade state account.
+    /// Programmable NFT listings revoke the delegate through token metadata using the accounts passed as remaining accounts.
+    pub fn cancel<'info>(
+        ctx: Context<'_, '_, '_, 'info, Cancel<'info>>,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        cancel::cancel(ctx, buyer_price, token_size)
+    }
+
+    /// Deposit `amount` into the escrow payment account for your specific wallet.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        escrow_payment_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        deposit::deposit(ctx, escrow_payment_bump, amount)
+    }
+
+    /// Withdraw `amount` from the escrow payment account for your specific wallet.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        escrow_payment_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        withdraw::withdraw(ctx, escrow_payment_bump, amount)
+    }
+
+    /// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
+    /// Programmable NFTs are transferred through token metadata using the accounts passed after the creators in the remaining accounts.
+    pub fn execute_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+        escrow_payment_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        execute_sale::execute_sale(
+            ctx,
+            escrow_payment_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
     pub fn auctioneer_buy<'info>(
         ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
         trade_state_bump: u8,
@@ -334,6 +437,46 @@ pub mod auction_house {
         )
     }
 
+    /// Execute a partial sale of `partial_order_size` tokens at `partial_order_price` out of a multi-unit listing.
+    pub fn execute_partial_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+        escrow_payment_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        partial_order_size: Option<u64>,
+        partial_order_price: Option<u64>,
+    ) -> Result<()> {
+        execute_sale::execute_partial_sale(
+            ctx,
+            escrow_payment_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            partial_order_size,
+            partial_order_price,
+        )
+    }
+
+    /// Create a public buy bid through the auctioneer, matchable against whichever wallet holds the token.
+    pub fn auctioneer_public_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        bid::auctioneer_public_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
     /// Cancel, but with an auctioneer
     pub fn auctioneer_cancel<'info>(
         ctx: Context<'_, '_, '_, 'info, AuctioneerCancel<'info>>,
@@ -431,6 +574,44 @@ pub mod auction_house {
     ) -> Result<()> {
         auctioneer::delegate_auctioneer(ctx)
     }
+
+    /// Create a listing receipt for the sell instruction executed right before it in the same transaction.
+    pub fn print_listing_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintListingReceipt<'info>>,
+        receipt_bump: u8,
+    ) -> Result<()> {
+        receipt::print_listing_receipt(ctx, receipt_bump)
+    }
+
+    /// Set `canceled_at` on a listing receipt after its listing is canceled in the same transaction.
+    pub fn cancel_listing_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelListingReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::cancel_listing_receipt(ctx)
+    }
+
+    /// Create a bid receipt for the buy or public buy instruction executed right before it in the same transaction.
+    pub fn print_bid_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintBidReceipt<'info>>,
+        receipt_bump: u8,
+    ) -> Result<()> {
+        receipt::print_bid_receipt(ctx, receipt_bump)
+    }
+
+    /// Set `canceled_at` on a bid receipt after its bid is canceled in the same transaction.
+    pub fn cancel_bid_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelBidReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::cancel_bid_receipt(ctx)
+    }
+
+    /// Create a purchase receipt for the sale executed right before it in the same transaction.
+    pub fn print_purchase_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintPurchaseReceipt<'info>>,
+        purchase_receipt_bump: u8,
+    ) -> Result<()> {
+        receipt::print_purchase_receipt(ctx, purchase_receipt_bump)
+    }
 }
 
 /// Accounts for the [`create_auction_house` handler](auction_house/fn.create_auction_house.html).
@@ -0,0 +1,387 @@
+use crate::{constants::*, errors::*, utils::*, *};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, sysvar},
+    AnchorDeserialize, Discriminator,
+};
+
+#[derive(Accounts)]
+#[instruction(receipt_bump: u8)]
+pub struct PrintListingReceipt<'info> {
+    /// CHECK: Validated in print_listing_receipt.
+    /// Listing receipt PDA account derived from the seller trade state of the preceding sell.
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    /// Payer of the receipt account rent.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address constrained to the instructions sysvar.
+    /// Instructions sysvar used to inspect the preceding sell instruction.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListingReceipt<'info> {
+    /// Listing receipt of the trade state canceled by the preceding instruction.
+    #[account(mut)]
+    pub receipt: Account<'info, ListingReceipt>,
+
+    /// CHECK: Address constrained to the instructions sysvar.
+    /// Instructions sysvar used to inspect the preceding cancel instruction.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(receipt_bump: u8)]
+pub struct PrintBidReceipt<'info> {
+    /// CHECK: Validated in print_bid_receipt.
+    /// Bid receipt PDA account derived from the buyer trade state of the preceding bid.
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    /// Payer of the receipt account rent.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address constrained to the instructions sysvar.
+    /// Instructions sysvar used to inspect the preceding bid instruction.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBidReceipt<'info> {
+    /// Bid receipt of the trade state canceled by the preceding instruction.
+    #[account(mut)]
+    pub receipt: Account<'info, BidReceipt>,
+
+    /// CHECK: Address constrained to the instructions sysvar.
+    /// Instructions sysvar used to inspect the preceding cancel instruction.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(purchase_receipt_bump: u8)]
+pub struct PrintPurchaseReceipt<'info> {
+    /// CHECK: Validated in print_purchase_receipt.
+    /// Purchase receipt PDA account derived from the seller and buyer trade states of the preceding sale.
+    #[account(mut)]
+    pub purchase_receipt: UncheckedAccount<'info>,
+
+    /// Listing receipt of the seller trade state matched by the sale.
+    #[account(mut)]
+    pub listing_receipt: Box<Account<'info, ListingReceipt>>,
+
+    /// Bid receipt of the buyer trade state matched by the sale.
+    #[account(mut)]
+    pub bid_receipt: Box<Account<'info, BidReceipt>>,
+
+    /// Payer of the receipt account rent.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address constrained to the instructions sysvar.
+    /// Instructions sysvar used to inspect the preceding execute sale instruction.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Create a listing receipt for the `sell` instruction executed right before it in the same transaction.
+pub fn print_listing_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintListingReceipt<'info>>,
+    receipt_bump: u8,
+) -> Result<()> {
+    let receipt_account = &ctx.accounts.receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+    let instruction = &ctx.accounts.instruction;
+
+    let prev_instruction = assert_previous_instruction(
+        &instruction.to_account_info(),
+        &[crate::instruction::Sell::DISCRIMINATOR],
+    )?;
+    let args = crate::instruction::Sell::try_from_slice(&prev_instruction.data[8..])
+        .map_err(|_| AuctionHouseError::InstructionMismatch)?;
+
+    let seller = instruction_account(&prev_instruction, 0)?;
+    let metadata = instruction_account(&prev_instruction, 2)?;
+    let auction_house = instruction_account(&prev_instruction, 4)?;
+    let trade_state = instruction_account(&prev_instruction, 6)?;
+
+    let receipt_info = receipt_account.to_account_info();
+    create_receipt_account(
+        &receipt_info,
+        bookkeeper,
+        system_program,
+        rent,
+        LISTING_RECEIPT_SIZE,
+        &[LISTING_RECEIPT_PREFIX.as_bytes(), trade_state.as_ref()],
+        receipt_bump,
+    )?;
+
+    let receipt = ListingReceipt {
+        trade_state,
+        bookkeeper: bookkeeper.key(),
+        auction_house,
+        seller,
+        metadata,
+        purchase_receipt: None,
+        price: args.buyer_price,
+        token_size: args.token_size,
+        bump: receipt_bump,
+        trade_state_bump: args.trade_state_bump,
+        created_at: Clock::get()?.unix_timestamp,
+        canceled_at: None,
+    };
+
+    let mut data = receipt_info.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    receipt.try_serialize(&mut dst)?;
+
+    Ok(())
+}
+
+/// Mark a listing receipt as canceled by the `cancel` instruction executed right before it in the same transaction.
+pub fn cancel_listing_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelListingReceipt<'info>>,
+) -> Result<()> {
+    let receipt = &mut ctx.accounts.receipt;
+    let instruction = &ctx.accounts.instruction;
+
+    let trade_state = assert_previous_cancel(&instruction.to_account_info())?;
+    assert_keys_equal(trade_state, receipt.trade_state)?;
+
+    receipt.canceled_at = Some(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+/// Create a bid receipt for the `buy` or `public_buy` instruction executed right before it in the same transaction.
+pub fn print_bid_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintBidReceipt<'info>>,
+    receipt_bump: u8,
+) -> Result<()> {
+    let receipt_account = &ctx.accounts.receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+    let instruction = &ctx.accounts.instruction;
+
+    let prev_instruction = assert_previous_instruction(
+        &instruction.to_account_info(),
+        &[
+            crate::instruction::Buy::DISCRIMINATOR,
+            crate::instruction::PublicBuy::DISCRIMINATOR,
+        ],
+    )?;
+    // Both bid instructions share the same argument layout.
+    let args = crate::instruction::Buy::try_from_slice(&prev_instruction.data[8..])
+        .map_err(|_| AuctionHouseError::InstructionMismatch)?;
+    let public = prev_instruction.data[..8] == crate::instruction::PublicBuy::DISCRIMINATOR;
+
+    let buyer = instruction_account(&prev_instruction, 0)?;
+    let token_account = instruction_account(&prev_instruction, 4)?;
+    let metadata = instruction_account(&prev_instruction, 5)?;
+    let auction_house = instruction_account(&prev_instruction, 8)?;
+    let trade_state = instruction_account(&prev_instruction, 10)?;
+
+    let receipt_info = receipt_account.to_account_info();
+    create_receipt_account(
+        &receipt_info,
+        bookkeeper,
+        system_program,
+        rent,
+        BID_RECEIPT_SIZE,
+        &[BID_RECEIPT_PREFIX.as_bytes(), trade_state.as_ref()],
+        receipt_bump,
+    )?;
+
+    let receipt = BidReceipt {
+        trade_state,
+        bookkeeper: bookkeeper.key(),
+        auction_house,
+        buyer,
+        metadata,
+        token_account: if public { None } else { Some(token_account) },
+        purchase_receipt: None,
+        price: args.buyer_price,
+        token_size: args.token_size,
+        bump: receipt_bump,
+        trade_state_bump: args.trade_state_bump,
+        created_at: Clock::get()?.unix_timestamp,
+        canceled_at: None,
+    };
+
+    let mut data = receipt_info.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    receipt.try_serialize(&mut dst)?;
+
+    Ok(())
+}
+
+/// Mark a bid receipt as canceled by the `cancel` instruction executed right before it in the same transaction.
+pub fn cancel_bid_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelBidReceipt<'info>>,
+) -> Result<()> {
+    let receipt = &mut ctx.accounts.receipt;
+    let instruction = &ctx.accounts.instruction;
+
+    let trade_state = assert_previous_cancel(&instruction.to_account_info())?;
+    assert_keys_equal(trade_state, receipt.trade_state)?;
+
+    receipt.canceled_at = Some(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+/// Create a purchase receipt for the `execute_sale` or `execute_partial_sale` instruction executed right before it in the same transaction, and link it from the matched listing and bid receipts.
+pub fn print_purchase_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintPurchaseReceipt<'info>>,
+    purchase_receipt_bump: u8,
+) -> Result<()> {
+    let purchase_receipt_account = &ctx.accounts.purchase_receipt;
+    let listing_receipt = &mut ctx.accounts.listing_receipt;
+    let bid_receipt = &mut ctx.accounts.bid_receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+    let instruction = &ctx.accounts.instruction;
+
+    let prev_instruction = assert_previous_instruction(
+        &instruction.to_account_info(),
+        &[
+            crate::instruction::ExecuteSale::DISCRIMINATOR,
+            crate::instruction::ExecutePartialSale::DISCRIMINATOR,
+        ],
+    )?;
+    let (price, token_size) =
+        if prev_instruction.data[..8] == crate::instruction::ExecutePartialSale::DISCRIMINATOR {
+            let args =
+                crate::instruction::ExecutePartialSale::try_from_slice(&prev_instruction.data[8..])
+                    .map_err(|_| AuctionHouseError::InstructionMismatch)?;
+            (
+                args.partial_order_price.unwrap_or(args.buyer_price),
+                args.partial_order_size.unwrap_or(args.token_size),
+            )
+        } else {
+            let args = crate::instruction::ExecuteSale::try_from_slice(&prev_instruction.data[8..])
+                .map_err(|_| AuctionHouseError::InstructionMismatch)?;
+            (args.buyer_price, args.token_size)
+        };
+
+    let buyer = instruction_account(&prev_instruction, 0)?;
+    let seller = instruction_account(&prev_instruction, 1)?;
+    let metadata = instruction_account(&prev_instruction, 4)?;
+    let auction_house = instruction_account(&prev_instruction, 10)?;
+    let buyer_trade_state = instruction_account(&prev_instruction, 13)?;
+    let seller_trade_state = instruction_account(&prev_instruction, 14)?;
+
+    assert_keys_equal(listing_receipt.trade_state, seller_trade_state)?;
+    assert_keys_equal(bid_receipt.trade_state, buyer_trade_state)?;
+
+    let purchase_receipt_info = purchase_receipt_account.to_account_info();
+    create_receipt_account(
+        &purchase_receipt_info,
+        bookkeeper,
+        system_program,
+        rent,
+        PURCHASE_RECEIPT_SIZE,
+        &[
+            PURCHASE_RECEIPT_PREFIX.as_bytes(),
+            seller_trade_state.as_ref(),
+            buyer_trade_state.as_ref(),
+        ],
+        purchase_receipt_bump,
+    )?;
+
+    let purchase_receipt = PurchaseReceipt {
+        bookkeeper: bookkeeper.key(),
+        buyer,
+        seller,
+        auction_house,
+        metadata,
+        token_size,
+        price,
+        bump: purchase_receipt_bump,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+
+    let mut data = purchase_receipt_info.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    purchase_receipt.try_serialize(&mut dst)?;
+
+    listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+    bid_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+
+    Ok(())
+}
+
+/// Verify the receipt PDA and bump, and allocate it unless a receipt already lives at that address.
+fn create_receipt_account<'info>(
+    receipt: &AccountInfo<'info>,
+    bookkeeper: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    size: usize,
+    path: &[&[u8]],
+    receipt_bump: u8,
+) -> Result<()> {
+    let canonical_bump = assert_derivation(&crate::id(), receipt, path)?;
+    if canonical_bump != receipt_bump {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    if receipt.data_is_empty() {
+        let bump_seed = [receipt_bump];
+        let mut receipt_seeds = path.to_vec();
+        receipt_seeds.push(&bump_seed);
+
+        create_or_allocate_account_raw(
+            crate::id(),
+            receipt,
+            &rent.to_account_info(),
+            &system_program.to_account_info(),
+            &bookkeeper.to_account_info(),
+            size,
+            &[],
+            &receipt_seeds,
+        )?;
+    } else if receipt.owner != &crate::id() {
+        return Err(AuctionHouseError::IncorrectOwner.into());
+    }
+
+    Ok(())
+}
+
+/// Verify the preceding instruction is a `cancel` and return the trade state it closed.
+fn assert_previous_cancel(instruction_sysvar: &AccountInfo) -> Result<Pubkey> {
+    let prev_instruction = assert_previous_instruction(
+        instruction_sysvar,
+        &[crate::instruction::Cancel::DISCRIMINATOR],
+    )?;
+
+    instruction_account(&prev_instruction, 6)
+}
+
+fn instruction_account(instruction: &Instruction, index: usize) -> Result<Pubkey> {
+    instruction
+        .accounts
+        .get(index)
+        .map(|account| account.pubkey)
+        .ok_or_else(|| AuctionHouseError::InstructionMismatch.into())
+}
@@ -1,8 +1,129 @@
 use anchor_lang::{prelude::*, solana_program::program::invoke, AnchorDeserialize};
+use mpl_token_metadata::instruction::{
+    builders::DelegateBuilder, DelegateArgs, InstructionBuilder,
+};
 use spl_token::instruction::approve;
 
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
 
+#[derive(Accounts, Clone)]
+#[instruction(
+    trade_state_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct Sell<'info> {
+    /// CHECK: Wallet is validated as a signer in sell_logic.
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// SPL token account containing token for sale.
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated by assert_metadata_valid.
+    /// Metaplex metadata account decorating SPL mint account.
+    /// Writable so token metadata can update programmable NFTs.
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Verified through CPI
+    /// Auction House authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Seller trade state PDA account encoding the sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Free seller trade state PDA account encoding a free sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &0u64.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub free_seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> From<AuctioneerSell<'info>> for Sell<'info> {
+    fn from(a: AuctioneerSell<'info>) -> Sell<'info> {
+        Sell {
+            wallet: a.wallet,
+            token_account: a.token_account,
+            metadata: a.metadata,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            seller_trade_state: a.seller_trade_state,
+            free_seller_trade_state: a.free_seller_trade_state,
+            program_as_signer: a.program_as_signer,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            rent: a.rent,
+        }
+    }
+}
+
 #[derive(Accounts, Clone)]
 #[instruction(
     trade_state_bump: u8,
@@ -22,6 +143,7 @@ pub struct AuctioneerSell<'info> {
 
     /// CHECK: Validated by assert_metadata_valid.
     /// Metaplex metadata account decorating SPL mint account.
+    /// Read-only so existing auctioneer programs keep working, programmable NFTs need it writable.
     pub metadata: UncheckedAccount<'info>,
 
     /// CHECK: Verified through CPI
@@ -115,6 +237,55 @@ pub struct AuctioneerSell<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
+pub fn sell<'info>(
+    ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+    trade_state_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let trade_state_canonical_bump = *ctx
+        .bumps
+        .get("seller_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let free_trade_state_canonical_bump = *ctx
+        .bumps
+        .get("free_seller_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_canonical_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (trade_state_canonical_bump != trade_state_bump)
+        || (free_trade_state_canonical_bump != free_trade_state_bump)
+        || (program_as_signer_canonical_bump != program_as_signer_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    let mut accounts: Sell<'info> = (*ctx.accounts).clone();
+
+    sell_logic(
+        &mut accounts,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        trade_state_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+    )
+}
+
 /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
 pub fn auctioneer_sell<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerSell<'info>>,
@@ -149,11 +320,12 @@ pub fn auctioneer_sell<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    let mut accounts: AuctioneerSell<'info> = (*ctx.accounts).clone();
+    let mut accounts: Sell<'info> = (*ctx.accounts).clone().into();
 
     sell_logic(
         &mut accounts,
         ctx.program_id,
+        ctx.remaining_accounts,
         trade_state_bump,
         free_trade_state_bump,
         program_as_signer_bump,
@@ -163,10 +335,10 @@ pub fn auctioneer_sell<'info>(
 }
 
 /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
-#[allow(clippy::extra_unused_lifetimes)]
 fn sell_logic<'c, 'info>(
-    accounts: &mut AuctioneerSell<'info>,
+    accounts: &mut Sell<'info>,
     program_id: &Pubkey,
+    remaining_accounts: &'c [AccountInfo<'info>],
     trade_state_bump: u8,
     _free_trade_state_bump: u8,
     _program_as_signer_bump: u8,
@@ -227,23 +399,90 @@ fn sell_logic<'c, 'info>(
     }
 
     if wallet.is_signer {
-        invoke(
-            &approve(
-                &token_program.key(),
-                &token_account.key(),
-                &program_as_signer.key(),
-                &wallet.key(),
-                &[],
-                token_size,
-            )
-            .unwrap(),
-            &[
-                token_program.to_account_info(),
-                token_account.to_account_info(),
-                program_as_signer.to_account_info(),
-                wallet.to_account_info(),
-            ],
-        )?;
+        if is_programmable(&metadata.to_account_info())? {
+            // Programmable NFTs are delegated through token metadata so the rule set is enforced.
+            // Remaining accounts: token metadata program, token mint, master edition, token record,
+            // authorization rules program, authorization rules and the instructions sysvar.
+            if !metadata.is_writable {
+                return Err(AuctionHouseError::MetadataNotWritable.into());
+            }
+            if remaining_accounts.len() < 7 {
+                return Err(AuctionHouseError::MissingProgrammableAccounts.into());
+            }
+
+            let remaining_accounts = &mut remaining_accounts.iter();
+            let token_metadata_program = next_account_info(remaining_accounts)?;
+            let token_mint = next_account_info(remaining_accounts)?;
+            let edition = next_account_info(remaining_accounts)?;
+            let token_record = next_account_info(remaining_accounts)?;
+            let auth_rules_program = next_account_info(remaining_accounts)?;
+            let auth_rules = next_account_info(remaining_accounts)?;
+            let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+            assert_keys_equal(token_metadata_program.key(), mpl_token_metadata::id())?;
+            assert_keys_equal(token_mint.key(), token_account.mint)?;
+
+            let mut delegate_builder = DelegateBuilder::new();
+            delegate_builder
+                .delegate(program_as_signer.key())
+                .metadata(metadata.key())
+                .master_edition(edition.key())
+                .token_record(token_record.key())
+                .mint(token_mint.key())
+                .token(token_account.key())
+                .authority(wallet.key())
+                .payer(wallet.key())
+                .system_program(system_program.key())
+                .sysvar_instructions(sysvar_instructions.key())
+                .spl_token_program(token_program.key())
+                .authorization_rules_program(auth_rules_program.key())
+                .authorization_rules(auth_rules.key());
+
+            let delegate_ix = delegate_builder
+                .build(DelegateArgs::SaleV1 {
+                    amount: token_size,
+                    authorization_data: None,
+                })
+                .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?
+                .instruction();
+
+            invoke(
+                &delegate_ix,
+                &[
+                    token_metadata_program.clone(),
+                    program_as_signer.to_account_info(),
+                    metadata.to_account_info(),
+                    edition.clone(),
+                    token_record.clone(),
+                    token_mint.clone(),
+                    token_account.to_account_info(),
+                    wallet.to_account_info(),
+                    system_program.to_account_info(),
+                    sysvar_instructions.clone(),
+                    token_program.to_account_info(),
+                    auth_rules_program.clone(),
+                    auth_rules.clone(),
+                ],
+            )?;
+        } else {
+            invoke(
+                &approve(
+                    &token_program.key(),
+                    &token_account.key(),
+                    &program_as_signer.key(),
+                    &wallet.key(),
+                    &[],
+                    token_size,
+                )
+                .unwrap(),
+                &[
+                    token_program.to_account_info(),
+                    token_account.to_account_info(),
+                    program_as_signer.to_account_info(),
+                    wallet.to_account_info(),
+                ],
+            )?;
+        }
     }
 
     let ts_info = seller_trade_state.to_account_info();
@@ -25,3 +25,49 @@ pub struct Auctioneer {
     pub auction_house: Pubkey,
     pub bump: u8,
 }
+
+#[account]
+pub struct ListingReceipt {
+    pub trade_state: Pubkey,
+    pub bookkeeper: Pubkey,
+    pub auction_house: Pubkey,
+    pub seller: Pubkey,
+    pub metadata: Pubkey,
+    pub purchase_receipt: Option<Pubkey>,
+    pub price: u64,
+    pub token_size: u64,
+    pub bump: u8,
+    pub trade_state_bump: u8,
+    pub created_at: i64,
+    pub canceled_at: Option<i64>,
+}
+
+#[account]
+pub struct BidReceipt {
+    pub trade_state: Pubkey,
+    pub bookkeeper: Pubkey,
+    pub auction_house: Pubkey,
+    pub buyer: Pubkey,
+    pub metadata: Pubkey,
+    pub token_account: Option<Pubkey>,
+    pub purchase_receipt: Option<Pubkey>,
+    pub price: u64,
+    pub token_size: u64,
+    pub bump: u8,
+    pub trade_state_bump: u8,
+    pub created_at: i64,
+    pub canceled_at: Option<i64>,
+}
+
+#[account]
+pub struct PurchaseReceipt {
+    pub bookkeeper: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub auction_house: Pubkey,
+    pub metadata: Pubkey,
+    pub token_size: u64,
+    pub price: u64,
+    pub bump: u8,
+    pub created_at: i64,
+}
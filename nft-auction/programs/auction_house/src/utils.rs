@@ -3,16 +3,18 @@ use crate::{errors::AuctionHouseError, AuctionHouse, PREFIX};
 use anchor_lang::{
     prelude::*,
     solana_program::{
+        instruction::Instruction,
         program::invoke_signed,
         program_memory::{sol_memcmp, sol_memset},
         program_pack::{IsInitialized, Pack},
         pubkey::PUBKEY_BYTES,
         system_instruction,
+        sysvar::instructions::get_instruction_relative,
     },
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use arrayref::array_ref;
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount, TokenStandard};
 use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
 use std::{convert::TryInto, slice::Iter};
 
@@ -87,6 +89,17 @@ pub fn assert_metadata_valid<'a>(
     Ok(())
 }
 
+/// Returns whether the metadata belongs to a programmable NFT, whose delegates and transfers have to go
+/// through token metadata instead of the SPL token program.
+pub fn is_programmable(metadata: &AccountInfo) -> Result<bool> {
+    let metadata = Metadata::from_account_info(metadata)?;
+
+    Ok(matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    ))
+}
+
 pub fn get_fee_payer<'a, 'b>(
     authority: &UncheckedAccount,
     wallet: AccountInfo<'a>,
@@ -543,3 +556,23 @@ pub fn close_account<'a>(
 
     Ok(())
 }
+
+/// Loads the instruction executed right before the current one from the instructions sysvar and
+/// verifies that it is an Auction House instruction with one of the given discriminators.
+pub fn assert_previous_instruction(
+    instruction_sysvar: &AccountInfo,
+    discriminators: &[[u8; 8]],
+) -> Result<Instruction> {
+    let prev_instruction = get_instruction_relative(-1, instruction_sysvar)?;
+
+    if prev_instruction.program_id != crate::id()
+        || prev_instruction.data.len() < 8
+        || !discriminators
+            .iter()
+            .any(|discriminator| prev_instruction.data[..8] == discriminator[..])
+    {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    Ok(prev_instruction)
+}
@@ -0,0 +1,208 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::{
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::token::spl_token;
+use auction_house::pda::*;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(buy_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(buyer_trade_state_account.data.len(), 1);
+    assert!(escrow_payment_account.lamports >= bid_amount);
+}
+
+#[tokio::test]
+async fn public_buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = public_buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(buyer_trade_state_account.data.len(), 1);
+}
+
+#[tokio::test]
+async fn auctioneer_public_buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    // DelegateAuctioneer
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let delegate_tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+    context
+        .banks_client
+        .process_transaction(delegate_tx)
+        .await
+        .unwrap();
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    // AuctioneerPublicBuy
+
+    let buyer_price = ONE_SOL;
+
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(&auction_house, &buyer.pubkey());
+    let (buyer_trade_state, buyer_trade_state_bump) = find_public_bid_trade_state_address(
+        &buyer.pubkey(),
+        &auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        1,
+    );
+
+    let data = auction_house::instruction::AuctioneerPublicBuy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        buyer_price,
+        token_size: 1,
+    };
+
+    let accounts = auction_house::accounts::AuctioneerPublicBuy {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auctioneer_authority: auctioneer_authority.pubkey(),
+        auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        auctioneer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &auctioneer_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buyer_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(buyer_trade_state_account.data.len(), 1);
+}
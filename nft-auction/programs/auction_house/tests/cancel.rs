@@ -0,0 +1,113 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn cancel_sell_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .expect("Failed to cancel");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .expect("Failed to get account");
+
+    assert!(seller_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn cancel_buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        &buy_accounts.buyer_trade_state,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .expect("Failed to cancel");
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .expect("Failed to get account");
+
+    assert!(buyer_trade_state_account.is_none());
+}
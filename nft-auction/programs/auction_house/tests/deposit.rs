@@ -0,0 +1,48 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn deposit_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let amount = ONE_SOL;
+
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        amount,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .expect("Failed to deposit");
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    assert_eq!(
+        escrow_payment_account.lamports,
+        amount + rent.minimum_balance(0)
+    );
+}
@@ -0,0 +1,288 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use auction_house::errors::AuctionHouseError;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn execute_partial_sale_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token_size = 3;
+    let token = create_sft(&mut context, token_size)
+        .await
+        .expect("Failed to create SFT");
+
+    // Sell
+
+    let listing_price = 3 * ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        token_size,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_order_size = 1;
+    let partial_order_price = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        partial_order_price,
+        partial_order_size,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy SFT");
+
+    // ExecutePartialSale
+
+    let (execute_sale_accounts, execute_sale_tx) = execute_partial_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        token_size,
+        Some(partial_order_size),
+        Some(partial_order_price),
+        &buyer,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .expect("Failed to execute partial sale");
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_receipt_token_account)
+        .await
+        .unwrap()
+        .expect("Buyer token account not found");
+    let buyer_token_account = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    let seller_token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .unwrap()
+        .expect("Seller token account not found");
+    let seller_token_account =
+        spl_token::state::Account::unpack(&seller_token_account.data).unwrap();
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+
+    assert_eq!(buyer_token_account.amount, partial_order_size);
+    assert_eq!(seller_token_account.amount, token_size - partial_order_size);
+    assert_eq!(
+        seller_token_account.delegated_amount,
+        token_size - partial_order_size
+    );
+    assert!(seller_trade_state_account.is_some());
+    assert!(buyer_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_execute_partial_sale_price_mismatch() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token_size = 3;
+    let token = create_sft(&mut context, token_size)
+        .await
+        .expect("Failed to create SFT");
+
+    // Sell
+
+    let listing_price = 3 * ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        token_size,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Buy below the listing price per token
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_order_size = 1;
+    let partial_order_price = ONE_SOL / 2;
+
+    let (buy_accounts, buy_tx) = buy_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        partial_order_price,
+        partial_order_size,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy SFT");
+
+    // ExecutePartialSale
+
+    let (_, execute_sale_tx) = execute_partial_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        token_size,
+        Some(partial_order_size),
+        Some(partial_order_price),
+        &buyer,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::PartialPriceMismatch.into());
+}
+
+#[tokio::test]
+async fn failure_execute_partial_sale_price_not_divisible() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token_size = 3;
+    let token = create_sft(&mut context, token_size)
+        .await
+        .expect("Failed to create SFT");
+
+    // Sell
+
+    // Listing price is not divisible by the listed amount
+    let listing_price = 10;
+
+    let (sell_accounts, sell_tx) = sell_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        token_size,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Buy at the rounded down price per token
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_order_size = 1;
+    let partial_order_price = 3;
+
+    let (buy_accounts, buy_tx) = buy_with_size(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        partial_order_price,
+        partial_order_size,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy SFT");
+
+    // ExecutePartialSale
+
+    let (_, execute_sale_tx) = execute_partial_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        token_size,
+        Some(partial_order_size),
+        Some(partial_order_price),
+        &buyer,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::PartialPriceMismatch.into());
+}
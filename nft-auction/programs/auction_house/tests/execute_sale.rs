@@ -0,0 +1,325 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::{error::ErrorCode, solana_program::program_pack::Pack};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn execute_sale_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let seller_before = context
+        .banks_client
+        .get_account(token.owner.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // ExecuteSale
+
+    let (execute_sale_accounts, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    )
+    .await;
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let seller_after = context
+        .banks_client
+        .get_account(token.owner.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_receipt_token_account)
+        .await
+        .unwrap()
+        .expect("Buyer token account not found");
+    let buyer_token_account = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+
+    assert_eq!(buyer_token_account.amount, 1);
+    assert!(seller_after.lamports > seller_before.lamports);
+    assert!(seller_trade_state_account.is_none());
+    assert!(buyer_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_execute_sale_price_mismatch() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    // ExecuteSale
+
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        bid_amount,
+        &buyer,
+    )
+    .await;
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::ConstraintSeeds.into());
+}
+
+#[tokio::test]
+async fn execute_sale_public_bid_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // PublicBuy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = public_buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    // Transfer NFT to a new holder after the bid was placed
+
+    let holder = Keypair::new();
+    airdrop(&mut context, &holder.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let holder_ata = get_associated_token_address(&holder.pubkey(), &token.mint.pubkey());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &token.owner.pubkey(),
+                &holder.pubkey(),
+                &token.mint.pubkey(),
+                &spl_token::id(),
+            ),
+            spl_token::instruction::transfer(
+                &spl_token::id(),
+                &token.ata,
+                &holder_ata,
+                &token.owner.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap(),
+        ],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to transfer NFT");
+
+    let token = NFT {
+        mint: token.mint,
+        owner: holder,
+        ata: holder_ata,
+        metadata: token.metadata,
+        master_edition: token.master_edition,
+    };
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // ExecuteSale
+
+    let (execute_sale_accounts, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    )
+    .await;
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_receipt_token_account)
+        .await
+        .unwrap()
+        .expect("Buyer token account not found");
+    let buyer_token_account = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+
+    assert_eq!(buyer_token_account.amount, 1);
+    assert!(buyer_trade_state_account.is_none());
+}
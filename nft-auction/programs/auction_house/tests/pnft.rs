@@ -0,0 +1,309 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::{program_option::COption, program_pack::Pack};
+use anchor_spl::token::spl_token;
+use auction_house::errors::AuctionHouseError;
+use auction_house::pda::find_program_as_signer_address;
+use nft_minter::utils::Creator;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_pnft_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context, None)
+        .await
+        .expect("Failed to create pNFT");
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    let tx = append_remaining_accounts(
+        &mut context,
+        sell_tx,
+        pnft_sell_accounts(&token),
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .unwrap()
+        .expect("Token account not found");
+    let token_account = spl_token::state::Account::unpack(&token_account.data).unwrap();
+
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    assert_eq!(seller_trade_state_account.data.len(), 1);
+    assert_eq!(token_account.delegate, COption::Some(program_as_signer));
+    assert!(token_account.is_frozen());
+}
+
+#[tokio::test]
+async fn cancel_pnft_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context, None)
+        .await
+        .expect("Failed to create pNFT");
+
+    let price = ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    let tx = append_remaining_accounts(
+        &mut context,
+        sell_tx,
+        pnft_sell_accounts(&token),
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        price,
+    );
+    let tx = append_remaining_accounts(
+        &mut context,
+        cancel_tx,
+        pnft_cancel_accounts(&token),
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to cancel");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .expect("Failed to get account");
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .unwrap()
+        .expect("Token account not found");
+    let token_account = spl_token::state::Account::unpack(&token_account.data).unwrap();
+
+    assert!(seller_trade_state_account.is_none());
+    assert_eq!(token_account.delegate, COption::None);
+}
+
+#[tokio::test]
+async fn failure_cancel_pnft_without_token_metadata_accounts() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context, None)
+        .await
+        .expect("Failed to create pNFT");
+
+    let price = ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    let tx = append_remaining_accounts(
+        &mut context,
+        sell_tx,
+        pnft_sell_accounts(&token),
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    // Cancel without the accounts needed to revoke the sale delegate
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        price,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(
+        tx_error,
+        AuctionHouseError::MissingProgrammableAccounts.into(),
+    );
+}
+
+#[tokio::test]
+async fn execute_sale_pnft_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let creator = Keypair::new();
+    let creators = vec![Creator {
+        address: creator.pubkey(),
+        verified: false,
+        share: 100,
+    }];
+
+    let token = create_pnft(&mut context, Some(creators.clone()))
+        .await
+        .expect("Failed to create pNFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    let tx = append_remaining_accounts(
+        &mut context,
+        sell_tx,
+        pnft_sell_accounts(&token),
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy pNFT");
+
+    // ExecuteSale
+
+    let (execute_sale_accounts, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        Some(creators),
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    )
+    .await;
+    let tx = append_remaining_accounts(
+        &mut context,
+        execute_sale_tx,
+        pnft_execute_sale_accounts(&token, &buyer.pubkey()),
+        &buyer,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_receipt_token_account)
+        .await
+        .unwrap()
+        .expect("Buyer token account not found");
+    let buyer_token_account = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    let creator_account = context
+        .banks_client
+        .get_account(creator.pubkey())
+        .await
+        .unwrap()
+        .expect("Creator account not found");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+
+    assert_eq!(buyer_token_account.amount, 1);
+    assert!(buyer_token_account.is_frozen());
+    // 10 basis points of royalties go to the only creator.
+    assert_eq!(creator_account.lamports, price / 1000);
+    assert!(seller_trade_state_account.is_none());
+}
@@ -0,0 +1,322 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::AccountDeserialize;
+use auction_house::{errors::AuctionHouseError, BidReceipt, ListingReceipt, PurchaseReceipt};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn print_listing_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    let (receipt_accounts, receipt_ix) =
+        print_listing_receipt(&token.owner.pubkey(), &sell_accounts.seller_trade_state);
+    let tx = append_instruction(&mut context, sell_tx, receipt_ix, &token.owner);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT and print listing receipt");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = ListingReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(receipt.trade_state, sell_accounts.seller_trade_state);
+    assert_eq!(receipt.bookkeeper, token.owner.pubkey());
+    assert_eq!(receipt.auction_house, auction_house);
+    assert_eq!(receipt.seller, token.owner.pubkey());
+    assert_eq!(receipt.metadata, token.metadata);
+    assert_eq!(receipt.purchase_receipt, None);
+    assert_eq!(receipt.price, ONE_SOL);
+    assert_eq!(receipt.token_size, 1);
+    assert_eq!(receipt.canceled_at, None);
+}
+
+#[tokio::test]
+async fn failure_print_listing_receipt_after_buy() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        ONE_SOL,
+    );
+    let (_, receipt_ix) = print_listing_receipt(&buyer.pubkey(), &buy_accounts.buyer_trade_state);
+    let tx = append_instruction(&mut context, buy_tx, receipt_ix, &buyer);
+    let error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_instruction_error(error, 1, AuctionHouseError::InstructionMismatch.into());
+}
+
+#[tokio::test]
+async fn cancel_listing_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    let (receipt_accounts, receipt_ix) =
+        print_listing_receipt(&token.owner.pubkey(), &sell_accounts.seller_trade_state);
+    let tx = append_instruction(&mut context, sell_tx, receipt_ix, &token.owner);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT and print listing receipt");
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        price,
+    );
+    let (_, cancel_receipt_ix) = cancel_listing_receipt(&receipt_accounts.receipt);
+    let tx = append_instruction(&mut context, cancel_tx, cancel_receipt_ix, &token.owner);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to cancel listing and its receipt");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = ListingReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert!(receipt.canceled_at.is_some());
+}
+
+#[tokio::test]
+async fn print_bid_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        ONE_SOL,
+    );
+    let (receipt_accounts, receipt_ix) =
+        print_bid_receipt(&buyer.pubkey(), &buy_accounts.buyer_trade_state);
+    let tx = append_instruction(&mut context, buy_tx, receipt_ix, &buyer);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT and print bid receipt");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = BidReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(receipt.trade_state, buy_accounts.buyer_trade_state);
+    assert_eq!(receipt.buyer, buyer.pubkey());
+    assert_eq!(receipt.token_account, Some(token.ata));
+    assert_eq!(receipt.price, ONE_SOL);
+    assert_eq!(receipt.token_size, 1);
+    assert_eq!(receipt.canceled_at, None);
+}
+
+#[tokio::test]
+async fn print_purchase_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    let (listing_receipt_accounts, listing_receipt_ix) =
+        print_listing_receipt(&token.owner.pubkey(), &sell_accounts.seller_trade_state);
+    let tx = append_instruction(&mut context, sell_tx, listing_receipt_ix, &token.owner);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT and print listing receipt");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    let (bid_receipt_accounts, bid_receipt_ix) =
+        print_bid_receipt(&buyer.pubkey(), &buy_accounts.buyer_trade_state);
+    let tx = append_instruction(&mut context, buy_tx, bid_receipt_ix, &buyer);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT and print bid receipt");
+
+    // ExecuteSale
+
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    )
+    .await;
+    let (purchase_receipt_accounts, purchase_receipt_ix) = print_purchase_receipt(
+        &buyer.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &buy_accounts.buyer_trade_state,
+    );
+    let tx = append_instruction(&mut context, execute_sale_tx, purchase_receipt_ix, &buyer);
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to execute sale and print purchase receipt");
+
+    let purchase_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_accounts.purchase_receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let purchase_receipt =
+        PurchaseReceipt::try_deserialize(&mut purchase_receipt_account.data.as_ref()).unwrap();
+
+    let listing_receipt_account = context
+        .banks_client
+        .get_account(listing_receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let listing_receipt =
+        ListingReceipt::try_deserialize(&mut listing_receipt_account.data.as_ref()).unwrap();
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(bid_receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(purchase_receipt.buyer, buyer.pubkey());
+    assert_eq!(purchase_receipt.seller, token.owner.pubkey());
+    assert_eq!(purchase_receipt.auction_house, auction_house);
+    assert_eq!(purchase_receipt.price, price);
+    assert_eq!(purchase_receipt.token_size, 1);
+    assert_eq!(
+        listing_receipt.purchase_receipt,
+        Some(purchase_receipt_accounts.purchase_receipt)
+    );
+    assert_eq!(
+        bid_receipt.purchase_receipt,
+        Some(purchase_receipt_accounts.purchase_receipt)
+    );
+}
@@ -0,0 +1,90 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use auction_house::{errors::AuctionHouseError, pda::find_auctioneer_address};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(seller_trade_state_account.data.len(), 1);
+}
+
+#[tokio::test]
+async fn failure_sell_auctioneer_delegated() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    // DelegateAuctioneer
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let delegate_tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+    context
+        .banks_client
+        .process_transaction(delegate_tx)
+        .await
+        .unwrap();
+
+    // Sell
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::MustUseAuctioneerHandler.into());
+}
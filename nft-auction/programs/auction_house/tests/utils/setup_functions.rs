@@ -2,14 +2,25 @@
 
 use anchor_client::solana_sdk::transaction::{Transaction, TransactionError};
 use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::prelude::AccountMeta;
 use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
 use anchor_lang::solana_program::{system_instruction, system_program, sysvar};
 use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 use anchor_lang::{InstructionData, ToAccountMetas};
-use anchor_spl::associated_token::{self};
+use anchor_spl::associated_token::{self, get_associated_token_address};
 use anchor_spl::token::spl_token;
 use auction_house::pda::*;
 use auction_house::AuctionHouse;
+use mpl_token_metadata::{
+    instruction::{
+        builders::{CreateBuilder, MintBuilder},
+        CreateArgs, InstructionBuilder, MintArgs,
+    },
+    pda::find_token_record_account,
+    state::{AssetData, PrintSupply, TokenStandard},
+};
+use nft_minter::pda::*;
+use nft_minter::utils::{into_mpl_creators, token_metadata_program_id, Creator};
 use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
 use std::io;
 
@@ -20,16 +31,29 @@ pub const ERR_INSUFFICIENT_FUNDS: u32 = 1;
 pub const ONE_SOL: u64 = 1_000_000_000;
 
 pub fn auction_house_program_test() -> ProgramTest {
-    let program = ProgramTest::new("auction_house", auction_house::id(), None);
+    let mut program = ProgramTest::new("auction_house", auction_house::id(), None);
+    program.add_program("nft_minter", nft_minter::id(), None);
+    program.add_program("mpl_token_metadata", token_metadata_program_id(), None);
     program
 }
 
 pub fn assert_error(error: BanksClientError, expected_error: u32) {
+    assert_instruction_error(error, 0, expected_error)
+}
+
+pub fn assert_instruction_error(
+    error: BanksClientError,
+    instruction_index: u8,
+    expected_error: u32,
+) {
     match error {
         BanksClientError::TransactionError(TransactionError::InstructionError(
-            0,
+            index,
             InstructionError::Custom(e),
-        )) => assert_eq!(e, expected_error),
+        )) => {
+            assert_eq!(index, instruction_index);
+            assert_eq!(e, expected_error)
+        }
         _ => assert!(false),
     }
 }
@@ -151,3 +175,1032 @@ pub async fn airdrop(
 
     Ok(())
 }
+
+#[derive(Debug)]
+pub struct NFT {
+    pub mint: Keypair,          // Mint
+    pub owner: Keypair,         // Seller
+    pub ata: Pubkey,            // Token account
+    pub metadata: Pubkey,       // Metaplex Metadata
+    pub master_edition: Pubkey, // Metaplex Master Edition
+}
+
+pub async fn create_nft(
+    context: &mut ProgramTestContext,
+    metadata_creators: Option<Vec<Creator>>,
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+    // CreateToken
+    let create_token_ix = Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::CreateToken {
+            name: "Solana Course NFT".to_string(),
+            symbol: "SOLC".to_string(),
+            uri: "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+                .to_string(),
+            creators: metadata_creators,
+            seller_fee_basis_points: 10,
+            is_mutable: false,
+        }
+        .data(),
+        accounts: nft_minter::accounts::CreateToken {
+            payer: owner.pubkey(),
+            mint_account: mint.pubkey(),
+            mint_authority: owner.pubkey(),
+            update_authority: owner.pubkey(),
+            metadata_account: metadata,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    };
+
+    // MintToken
+    let mint_token_ix = Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::MintToken {
+            max_supply: Some(0),
+        }
+        .data(),
+        accounts: nft_minter::accounts::MintToken {
+            payer: owner.pubkey(),
+            mint_account: mint.pubkey(),
+            mint_authority: owner.pubkey(),
+            update_authority: owner.pubkey(),
+            associated_token_account: ata,
+            metadata_account: metadata,
+            edition_account: master_edition,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_token_ix, mint_token_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+pub async fn create_pnft(
+    context: &mut ProgramTestContext,
+    metadata_creators: Option<Vec<Creator>>,
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+    let (token_record, _) = find_token_record_account(&mint.pubkey(), &ata);
+
+    let mut asset_data = AssetData::new(
+        TokenStandard::ProgrammableNonFungible,
+        "Solana Course pNFT".to_string(),
+        "SOLC".to_string(),
+        "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+            .to_string(),
+    );
+    asset_data.seller_fee_basis_points = 10;
+    asset_data.creators = into_mpl_creators(metadata_creators);
+
+    // CreateV1
+    let create_ix = CreateBuilder::new()
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .mint(mint.pubkey())
+        .authority(owner.pubkey())
+        .payer(owner.pubkey())
+        .update_authority(owner.pubkey())
+        .system_program(system_program::id())
+        .sysvar_instructions(sysvar::instructions::id())
+        .spl_token_program(spl_token::id())
+        .initialize_mint(true)
+        .update_authority_as_signer(true)
+        .build(CreateArgs::V1 {
+            asset_data,
+            decimals: Some(0),
+            print_supply: Some(PrintSupply::Zero),
+        })
+        .expect("Failed to build create instruction")
+        .instruction();
+
+    // MintV1
+    let mint_ix = MintBuilder::new()
+        .token(ata)
+        .token_owner(owner.pubkey())
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .token_record(token_record)
+        .mint(mint.pubkey())
+        .authority(owner.pubkey())
+        .payer(owner.pubkey())
+        .system_program(system_program::id())
+        .sysvar_instructions(sysvar::instructions::id())
+        .spl_token_program(spl_token::id())
+        .spl_ata_program(associated_token::ID)
+        .build(MintArgs::V1 {
+            amount: 1,
+            authorization_data: None,
+        })
+        .expect("Failed to build mint instruction")
+        .instruction();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, mint_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+pub async fn create_sft(
+    context: &mut ProgramTestContext,
+    amount: u64,
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+    // CreateToken
+    let create_token_ix = Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::CreateToken {
+            name: "Solana Course SFT".to_string(),
+            symbol: "SOLC".to_string(),
+            uri: "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+                .to_string(),
+            creators: None,
+            seller_fee_basis_points: 10,
+            is_mutable: false,
+        }
+        .data(),
+        accounts: nft_minter::accounts::CreateToken {
+            payer: owner.pubkey(),
+            mint_account: mint.pubkey(),
+            mint_authority: owner.pubkey(),
+            update_authority: owner.pubkey(),
+            metadata_account: metadata,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    };
+
+    // Mint `amount` tokens without a master edition
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &owner.pubkey(),
+        &owner.pubkey(),
+        &mint.pubkey(),
+        &spl_token::id(),
+    );
+    let mint_to_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &ata,
+        &owner.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_token_ix, create_ata_ix, mint_to_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+pub fn sell(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer_price: u64, // Listing price
+) -> (auction_house::accounts::Sell, Transaction) {
+    sell_with_size(
+        context,
+        auction_house,
+        auction_house_data,
+        token,
+        buyer_price,
+        1,
+    )
+}
+
+pub fn sell_with_size(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer_price: u64, // Listing price for the whole `token_size`
+    token_size: u64,
+) -> (auction_house::accounts::Sell, Transaction) {
+    let (seller_trade_state, seller_trade_state_bump) = find_trade_state_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        token_size,
+    );
+
+    let (free_seller_trade_state, free_seller_trade_state_bump) = find_trade_state_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        0,
+        token_size,
+    );
+
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::Sell {
+        trade_state_bump: seller_trade_state_bump,
+        free_trade_state_bump: free_seller_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auction_house::accounts::Sell {
+        wallet: token.owner.pubkey(),
+        token_account: token.ata,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        free_seller_trade_state,
+        program_as_signer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&token.owner.pubkey()),
+            &[&token.owner],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn buy(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    buyer_price: u64, // Bid amount
+) -> (auction_house::accounts::Buy, Transaction) {
+    buy_with_size(
+        context,
+        auction_house,
+        auction_house_data,
+        token,
+        buyer,
+        buyer_price,
+        1,
+    )
+}
+
+pub fn buy_with_size(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    buyer_price: u64, // Bid amount for the whole `token_size`
+    token_size: u64,
+) -> (auction_house::accounts::Buy, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (buyer_trade_state, buyer_trade_state_bump) = find_trade_state_address(
+        &buyer.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        token_size,
+    );
+
+    let data = auction_house::instruction::Buy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auction_house::accounts::Buy {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn public_buy(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    buyer_price: u64, // Bid amount
+) -> (auction_house::accounts::PublicBuy, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (buyer_trade_state, buyer_trade_state_bump) = find_public_bid_trade_state_address(
+        &buyer.pubkey(),
+        auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        1,
+    );
+
+    let data = auction_house::instruction::PublicBuy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        buyer_price,
+        token_size: 1,
+    };
+
+    let accounts = auction_house::accounts::PublicBuy {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub async fn execute_sale(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    metadata_creators: Option<Vec<Creator>>,
+    sell_accounts: &auction_house::accounts::Sell,
+    buyer: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    buyer_price: u64,
+    signer_payer: &Keypair,
+) -> (auction_house::accounts::ExecuteSale, Transaction) {
+    if signer_payer.pubkey().eq(&auction_house_data.authority) {
+        airdrop(
+            context,
+            &auction_house_data.auction_house_fee_account,
+            10 * ONE_SOL,
+        )
+        .await
+        .expect("Failed to airdrop SOLs to Auction House fee account");
+    }
+
+    let buyer_receipt_token_account = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (_, program_as_signer_bump) = find_program_as_signer_address();
+
+    let (_, free_seller_trade_state_bump) = find_trade_state_address(
+        &sell_accounts.wallet,
+        auction_house,
+        &sell_accounts.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        0,
+        1,
+    );
+
+    let data = auction_house::instruction::ExecuteSale {
+        escrow_payment_bump: escrow_payment_account_bump,
+        free_trade_state_bump: free_seller_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size: 1,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSale {
+        buyer: *buyer,
+        seller: sell_accounts.wallet,
+        token_account: sell_accounts.token_account,
+        token_mint: token.mint.pubkey(),
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: sell_accounts.wallet,
+        buyer_receipt_token_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state: sell_accounts.seller_trade_state,
+        free_trade_state: sell_accounts.free_seller_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer: sell_accounts.program_as_signer,
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    if let Some(creators) = metadata_creators {
+        for creator in &creators {
+            account_metas.push(AccountMeta {
+                pubkey: creator.address,
+                is_signer: false,
+                is_writable: true,
+            });
+        }
+    }
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer_payer.pubkey()),
+            &[signer_payer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn execute_partial_sale(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    sell_accounts: &auction_house::accounts::Sell,
+    buyer: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    buyer_price: u64, // Listing price for the whole `token_size`
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+    signer_payer: &Keypair,
+) -> (auction_house::accounts::ExecuteSale, Transaction) {
+    let buyer_receipt_token_account = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (_, program_as_signer_bump) = find_program_as_signer_address();
+
+    let (_, free_seller_trade_state_bump) = find_trade_state_address(
+        &sell_accounts.wallet,
+        auction_house,
+        &sell_accounts.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        0,
+        token_size,
+    );
+
+    let data = auction_house::instruction::ExecutePartialSale {
+        escrow_payment_bump: escrow_payment_account_bump,
+        free_trade_state_bump: free_seller_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSale {
+        buyer: *buyer,
+        seller: sell_accounts.wallet,
+        token_account: sell_accounts.token_account,
+        token_mint: token.mint.pubkey(),
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: sell_accounts.wallet,
+        buyer_receipt_token_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state: sell_accounts.seller_trade_state,
+        free_trade_state: sell_accounts.free_seller_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer: sell_accounts.program_as_signer,
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer_payer.pubkey()),
+            &[signer_payer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn deposit(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::Deposit, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+
+    let data = auction_house::instruction::Deposit {
+        escrow_payment_bump: escrow_payment_account_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Deposit {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn withdraw(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::Withdraw, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+
+    let data = auction_house::instruction::Withdraw {
+        escrow_payment_bump: escrow_payment_account_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Withdraw {
+        wallet: buyer.pubkey(),
+        receipt_account: buyer.pubkey(),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn cancel(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    wallet: &Keypair,
+    trade_state: &Pubkey,
+    buyer_price: u64,
+) -> (auction_house::accounts::Cancel, Transaction) {
+    let data = auction_house::instruction::Cancel {
+        buyer_price,
+        token_size: 1,
+    };
+
+    let accounts = auction_house::accounts::Cancel {
+        wallet: wallet.pubkey(),
+        token_account: token.ata,
+        token_mint: token.mint.pubkey(),
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        trade_state: *trade_state,
+        token_program: spl_token::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&wallet.pubkey()),
+            &[wallet],
+            context.last_blockhash,
+        ),
+    )
+}
+
+/// Rebuilds `transaction` with `instruction` appended, e.g. to print a receipt right after the
+/// instruction it records. `payer` must be the only signer of the original transaction.
+pub fn append_instruction(
+    context: &mut ProgramTestContext,
+    transaction: Transaction,
+    instruction: Instruction,
+    payer: &Keypair,
+) -> Transaction {
+    let mut instructions = decompile_instructions(&transaction);
+    instructions.push(instruction);
+
+    Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    )
+}
+
+/// Rebuilds `transaction` with `remaining_accounts` added to its last instruction, e.g. to pass the
+/// token metadata accounts of a programmable NFT. `payer` must be the only signer of the original
+/// transaction.
+pub fn append_remaining_accounts(
+    context: &mut ProgramTestContext,
+    transaction: Transaction,
+    remaining_accounts: Vec<AccountMeta>,
+    payer: &Keypair,
+) -> Transaction {
+    let mut instructions = decompile_instructions(&transaction);
+    instructions
+        .last_mut()
+        .expect("Transaction has no instructions")
+        .accounts
+        .extend(remaining_accounts);
+
+    Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    )
+}
+
+fn decompile_instructions(transaction: &Transaction) -> Vec<Instruction> {
+    let message = &transaction.message;
+    message
+        .instructions
+        .iter()
+        .map(|ix| Instruction {
+            program_id: message.account_keys[ix.program_id_index as usize],
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta {
+                    pubkey: message.account_keys[index as usize],
+                    is_signer: message.is_signer(index as usize),
+                    is_writable: message.is_writable(index as usize),
+                })
+                .collect(),
+            data: ix.data.clone(),
+        })
+        .collect()
+}
+
+pub fn print_listing_receipt(
+    bookkeeper: &Pubkey,
+    seller_trade_state: &Pubkey,
+) -> (auction_house::accounts::PrintListingReceipt, Instruction) {
+    let (receipt, receipt_bump) = find_listing_receipt_address(seller_trade_state);
+
+    let data = auction_house::instruction::PrintListingReceipt { receipt_bump };
+
+    let accounts = auction_house::accounts::PrintListingReceipt {
+        receipt,
+        bookkeeper: *bookkeeper,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn cancel_listing_receipt(
+    receipt: &Pubkey,
+) -> (auction_house::accounts::CancelListingReceipt, Instruction) {
+    let data = auction_house::instruction::CancelListingReceipt {};
+
+    let accounts = auction_house::accounts::CancelListingReceipt {
+        receipt: *receipt,
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn print_bid_receipt(
+    bookkeeper: &Pubkey,
+    buyer_trade_state: &Pubkey,
+) -> (auction_house::accounts::PrintBidReceipt, Instruction) {
+    let (receipt, receipt_bump) = find_bid_receipt_address(buyer_trade_state);
+
+    let data = auction_house::instruction::PrintBidReceipt { receipt_bump };
+
+    let accounts = auction_house::accounts::PrintBidReceipt {
+        receipt,
+        bookkeeper: *bookkeeper,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn cancel_bid_receipt(
+    receipt: &Pubkey,
+) -> (auction_house::accounts::CancelBidReceipt, Instruction) {
+    let data = auction_house::instruction::CancelBidReceipt {};
+
+    let accounts = auction_house::accounts::CancelBidReceipt {
+        receipt: *receipt,
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn print_purchase_receipt(
+    bookkeeper: &Pubkey,
+    seller_trade_state: &Pubkey,
+    buyer_trade_state: &Pubkey,
+) -> (auction_house::accounts::PrintPurchaseReceipt, Instruction) {
+    let (purchase_receipt, purchase_receipt_bump) =
+        find_purchase_receipt_address(seller_trade_state, buyer_trade_state);
+    let (listing_receipt, _) = find_listing_receipt_address(seller_trade_state);
+    let (bid_receipt, _) = find_bid_receipt_address(buyer_trade_state);
+
+    let data = auction_house::instruction::PrintPurchaseReceipt {
+        purchase_receipt_bump,
+    };
+
+    let accounts = auction_house::accounts::PrintPurchaseReceipt {
+        purchase_receipt,
+        listing_receipt,
+        bid_receipt,
+        bookkeeper: *bookkeeper,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+/// Remaining accounts `sell` needs to delegate a programmable NFT without a rule set.
+pub fn pnft_sell_accounts(token: &NFT) -> Vec<AccountMeta> {
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+
+    vec![
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token.mint.pubkey(), false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(token_record, false),
+        // Authorization rules program and rule set placeholders.
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]
+}
+
+/// Remaining accounts `cancel` needs to revoke the sale delegate of a programmable NFT without a rule set.
+pub fn pnft_cancel_accounts(token: &NFT) -> Vec<AccountMeta> {
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    vec![
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new(token.metadata, false),
+        AccountMeta::new_readonly(program_as_signer, false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(token_record, false),
+        // Authorization rules program and rule set placeholders.
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]
+}
+
+/// Remaining accounts `execute_sale` needs after the creators to transfer a programmable NFT without
+/// a rule set.
+pub fn pnft_execute_sale_accounts(token: &NFT, buyer: &Pubkey) -> Vec<AccountMeta> {
+    let buyer_token_account = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (owner_token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let (destination_token_record, _) =
+        find_token_record_account(&token.mint.pubkey(), &buyer_token_account);
+
+    vec![
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(owner_token_record, false),
+        AccountMeta::new(destination_token_record, false),
+        // Authorization rules program and rule set placeholders.
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]
+}
@@ -0,0 +1,116 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use auction_house::errors::AuctionHouseError;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn withdraw_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let amount = ONE_SOL;
+
+    let (_, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        amount,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .expect("Failed to deposit");
+
+    let buyer_before = context
+        .banks_client
+        .get_account(buyer.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (withdraw_accounts, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        amount,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .expect("Failed to withdraw");
+
+    let buyer_after = context
+        .banks_client
+        .get_account(buyer.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(withdraw_accounts.escrow_payment_account)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    assert!(buyer_after.lamports > buyer_before.lamports);
+    assert_eq!(escrow_payment_account.lamports, rent.minimum_balance(0));
+}
+
+#[tokio::test]
+async fn failure_withdraw_insufficient_funds() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .expect("Failed to deposit");
+
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        2 * ONE_SOL,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::InsufficientFunds.into());
+}
@@ -0,0 +1,322 @@
+//! Builders of ready to sign `nft_shop` instructions, every PDA is derived here.
+//! Canonical bumps are stored on chain, so instructions don't take bump arguments
+
+use crate::pda::*;
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        system_program, sysvar,
+    },
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::token::spl_token;
+use nft_shop::state::{
+    Allowlist, AllowlistProof, Gate, GateKind, Market, PriceTier, PricingCurve, SellingResource,
+};
+
+/// Arguments of `create_market` which are not accounts
+#[derive(Clone, Debug)]
+pub struct CreateMarketParams {
+    pub name: String,
+    pub description: String,
+    pub mutable: bool,
+    pub price: u64,
+    pub pieces_in_one_wallet: Option<u64>,
+    pub start_date: u64,
+    pub end_date: Option<u64>,
+    pub pricing_curve: Option<PricingCurve>,
+    pub allowlist: Option<Allowlist>,
+    pub referral_basis_points: u16,
+    pub allocation: Option<u64>,
+    pub gate: Option<Gate>,
+    pub price_tiers: Vec<PriceTier>,
+}
+
+pub fn create_store(
+    store: &Pubkey,
+    store_admin: &Pubkey,
+    name: String,
+    description: String,
+) -> Instruction {
+    let accounts = nft_shop::accounts::CreateStore {
+        store: *store,
+        store_admin: *store_admin,
+        system_program: system_program::id(),
+    };
+
+    let data = nft_shop::instruction::CreateStore { name, description };
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// `vault` should be a token account of `resource_mint` owned by the vault owner PDA
+pub fn init_selling_resource(
+    store: &Pubkey,
+    store_admin: &Pubkey,
+    selling_resource: &Pubkey,
+    selling_resource_owner: &Pubkey,
+    resource_mint: &Pubkey,
+    resource_token: &Pubkey,
+    vault: &Pubkey,
+    max_supply: Option<u64>,
+) -> Instruction {
+    let (vault_owner, _) = find_vault_owner_address(resource_mint, store);
+    let (metadata, _) = find_metadata_address(resource_mint);
+    let (master_edition, _) = find_master_edition_address(resource_mint);
+
+    let accounts = nft_shop::accounts::InitSellingResource {
+        store: *store,
+        store_admin: *store_admin,
+        selling_resource: *selling_resource,
+        selling_resource_owner: *selling_resource_owner,
+        resource_mint: *resource_mint,
+        resource_token: *resource_token,
+        metadata,
+        master_edition,
+        vault: *vault,
+        vault_owner,
+        system_program: system_program::id(),
+        token_program: spl_token::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let data = nft_shop::instruction::InitSellingResource { max_supply };
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// `treasury_holder` is `None` for native SOL `Market`, which keeps funds on treasury owner PDA
+pub fn create_market(
+    market: &Pubkey,
+    store: &Pubkey,
+    selling_resource: &Pubkey,
+    selling_resource_owner: &Pubkey,
+    treasury_mint: &Pubkey,
+    treasury_holder: Option<Pubkey>,
+    params: CreateMarketParams,
+) -> Instruction {
+    let (treasury_owner, _) = find_treasury_owner_address(treasury_mint, selling_resource, market);
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: *market,
+        store: *store,
+        selling_resource: *selling_resource,
+        selling_resource_owner: *selling_resource_owner,
+        treasury_mint: *treasury_mint,
+        treasury_holder: treasury_holder.unwrap_or(treasury_owner),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: params.name,
+        description: params.description,
+        mutable: params.mutable,
+        price: params.price,
+        pieces_in_one_wallet: params.pieces_in_one_wallet,
+        start_date: params.start_date,
+        end_date: params.end_date,
+        pricing_curve: params.pricing_curve,
+        allowlist: params.allowlist,
+        referral_basis_points: params.referral_basis_points,
+        allocation: params.allocation,
+        gate: params.gate,
+        price_tiers: params.price_tiers,
+    };
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Buy the next print of master edition, which is `edition` = master edition supply + 1.
+/// `treasury_token_program` is needed only for `Market`s paid in Token-2022 tokens.
+/// `gate_token` is the buyer's gate token account and its mint, needed only for gated `Market`s
+pub fn buy(
+    market: &Pubkey,
+    market_data: &Market,
+    selling_resource: &SellingResource,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    new_mint: &Pubkey,
+    new_token_account: &Pubkey,
+    edition: u64,
+    referrer: Option<&Pubkey>,
+    treasury_token_program: Option<&Pubkey>,
+    gate_token: Option<(&Pubkey, &Pubkey)>,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Instruction {
+    let (trade_history, _) = find_trade_history_address(user_wallet, market);
+    let (metadata, _) = find_metadata_address(&selling_resource.resource);
+    let (master_edition, _) = find_master_edition_address(&selling_resource.resource);
+    let (edition_marker, _) = find_edition_marker_address(&selling_resource.resource, edition);
+    let (new_metadata, _) = find_metadata_address(new_mint);
+    let (new_edition, _) = find_master_edition_address(new_mint);
+
+    let gate = market_data.gate.as_ref().zip(gate_token);
+    let gate_metadata = gate
+        .filter(|(gate, _)| gate.kind == GateKind::Collection)
+        .map(|(_, (_, gate_mint))| find_metadata_address(gate_mint).0);
+    let gate_usage = gate
+        .filter(|(gate, _)| gate.limit.is_some())
+        .map(|(_, (_, gate_mint))| find_gate_usage_address(market, gate_mint).0);
+
+    let accounts = nft_shop::accounts::Buy {
+        market: *market,
+        selling_resource: market_data.selling_resource,
+        user_token_account: *user_token_account,
+        user_wallet: *user_wallet,
+        trade_history,
+        treasury_holder: market_data.treasury_holder,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: *new_mint,
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner: selling_resource.vault_owner,
+        new_token_account: *new_token_account,
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: referrer.map(|referrer| find_referral_address(market, referrer).0),
+        treasury_mint: treasury_token_program.map(|_| market_data.treasury_mint),
+        treasury_token_program: treasury_token_program.copied(),
+        gate_token_account: gate.map(|(_, (gate_token_account, _))| *gate_token_account),
+        gate_metadata,
+        gate_usage,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let data = nft_shop::instruction::Buy { allowlist_proof };
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+pub fn close_market(market: &Pubkey, selling_resource_owner: &Pubkey) -> Instruction {
+    let accounts = nft_shop::accounts::CloseMarket {
+        market: *market,
+        selling_resource_owner: *selling_resource_owner,
+        clock: sysvar::clock::id(),
+    };
+
+    let data = nft_shop::instruction::CloseMarket {};
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Until `primary_sale_happened`, `PrimaryMetadataCreators` of the resource is passed
+/// to check, that creators have withdrawn their shares
+pub fn claim_resource(
+    market: &Pubkey,
+    market_data: &Market,
+    selling_resource: &SellingResource,
+    destination: &Pubkey,
+    primary_sale_happened: bool,
+) -> Instruction {
+    let (metadata, _) = find_metadata_address(&selling_resource.resource);
+
+    let accounts = nft_shop::accounts::ClaimResource {
+        market: *market,
+        store: selling_resource.store,
+        selling_resource: market_data.selling_resource,
+        selling_resource_owner: selling_resource.owner,
+        metadata,
+        treasury_holder: market_data.treasury_holder,
+        vault: selling_resource.vault,
+        vault_owner: selling_resource.vault_owner,
+        destination: *destination,
+        token_metadata_program: mpl_token_metadata::id(),
+        system_program: system_program::id(),
+        clock: sysvar::clock::id(),
+        token_program: spl_token::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    if !primary_sale_happened {
+        let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
+        account_metas.push(AccountMeta::new_readonly(primary_metadata_creators, false));
+    }
+
+    let data = nft_shop::instruction::ClaimResource {};
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Pay `funder` share of `Market` funds. `treasury_token_program` is ignored for native SOL `Market`.
+/// Until `primary_sale_happened`, `PrimaryMetadataCreators` of the resource is passed as well
+pub fn withdraw(
+    market: &Pubkey,
+    market_data: &Market,
+    selling_resource: &SellingResource,
+    funder: &Pubkey,
+    treasury_token_program: &Pubkey,
+    primary_sale_happened: bool,
+) -> Instruction {
+    let (payout_ticket, _) = find_payout_ticket_address(market, funder);
+    let (metadata, _) = find_metadata_address(&selling_resource.resource);
+
+    let destination = if market_data.treasury_mint == system_program::id() {
+        *funder
+    } else {
+        find_associated_token_address(funder, &market_data.treasury_mint, treasury_token_program).0
+    };
+
+    let accounts = nft_shop::accounts::Withdraw {
+        market: *market,
+        selling_resource: market_data.selling_resource,
+        metadata,
+        treasury_holder: market_data.treasury_holder,
+        treasury_mint: market_data.treasury_mint,
+        treasury_owner: market_data.treasury_owner,
+        destination,
+        funder: *funder,
+        selling_resource_owner: market_data.owner,
+        payout_ticket,
+        rent: sysvar::rent::id(),
+        clock: sysvar::clock::id(),
+        token_program: *treasury_token_program,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    if !primary_sale_happened {
+        let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
+        account_metas.push(AccountMeta::new_readonly(primary_metadata_creators, false));
+    }
+
+    let data = nft_shop::instruction::Withdraw {};
+
+    Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
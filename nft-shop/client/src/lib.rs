@@ -0,0 +1,8 @@
+//! Off-chain helpers to build `nft_shop` instructions and decode its accounts
+#![allow(clippy::too_many_arguments)]
+
+pub mod instruction;
+pub mod pda;
+pub mod state;
+
+pub use nft_shop::id;
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::Pubkey;
+use mpl_token_metadata::state::{EDITION, EDITION_MARKER_BIT_SIZE, PREFIX};
+
+pub use nft_shop::pda::*;
+
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+}
+
+pub fn find_master_edition_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+}
+
+/// Marker which tracks `edition` among the prints of master edition `mint`
+pub fn find_edition_marker_address(mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            (edition / EDITION_MARKER_BIT_SIZE).to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+}
+
+/// Associated token account of `wallet`, for mints of SPL Token or Token-2022 program
+pub fn find_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &anchor_spl::associated_token::ID,
+    )
+}
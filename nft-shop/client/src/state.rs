@@ -0,0 +1,18 @@
+use anchor_lang::{AccountDeserialize, Result};
+use nft_shop::state::{Market, SellingResource, Store, TradeHistory};
+
+pub fn decode_store(data: &[u8]) -> Result<Store> {
+    Store::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_market(data: &[u8]) -> Result<Market> {
+    Market::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_selling_resource(data: &[u8]) -> Result<SellingResource> {
+    SellingResource::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_trade_history(data: &[u8]) -> Result<TradeHistory> {
+    TradeHistory::try_deserialize(&mut &data[..])
+}
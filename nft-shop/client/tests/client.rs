@@ -0,0 +1,72 @@
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use nft_shop::state::TradeHistory;
+use nft_shop_client::{instruction, pda, state};
+
+#[test]
+fn decode_trade_history_success() {
+    let trade_history = TradeHistory {
+        market: Pubkey::new_unique(),
+        wallet: Pubkey::new_unique(),
+        already_bought: 2,
+        funds_paid: 42,
+    };
+
+    let mut data = Vec::new();
+    trade_history.try_serialize(&mut data).unwrap();
+
+    let decoded = state::decode_trade_history(&data).unwrap();
+
+    assert_eq!(decoded.market, trade_history.market);
+    assert_eq!(decoded.wallet, trade_history.wallet);
+    assert_eq!(decoded.already_bought, 2);
+    assert_eq!(decoded.funds_paid, 42);
+}
+
+#[test]
+fn failure_decode_market_from_other_account() {
+    let mut data = Vec::new();
+    TradeHistory::default().try_serialize(&mut data).unwrap();
+
+    assert!(state::decode_market(&data).is_err());
+}
+
+#[test]
+fn create_market_native_treasury_holder() {
+    let market = Pubkey::new_unique();
+    let store = Pubkey::new_unique();
+    let selling_resource = Pubkey::new_unique();
+    let selling_resource_owner = Pubkey::new_unique();
+    let treasury_mint = anchor_lang::solana_program::system_program::id();
+
+    let ix = instruction::create_market(
+        &market,
+        &store,
+        &selling_resource,
+        &selling_resource_owner,
+        &treasury_mint,
+        None,
+        instruction::CreateMarketParams {
+            name: "1234_1234_".to_string(),
+            description: "1234_1234_1234_1234_".to_string(),
+            mutable: true,
+            price: 1,
+            pieces_in_one_wallet: None,
+            start_date: 0,
+            end_date: None,
+            pricing_curve: None,
+            allowlist: None,
+            referral_basis_points: 0,
+            allocation: None,
+            gate: None,
+            price_tiers: vec![],
+        },
+    );
+
+    let (treasury_owner, _) =
+        pda::find_treasury_owner_address(&treasury_mint, &selling_resource, &market);
+
+    assert_eq!(ix.program_id, nft_shop::id());
+    // treasury_holder and treasury_owner are the same PDA for native SOL
+    assert_eq!(ix.accounts[5].pubkey, treasury_owner);
+    assert_eq!(ix.accounts[6].pubkey, treasury_owner);
+}
@@ -131,4 +131,128 @@ pub enum ErrorCode {
     // 6031
     #[msg("Invalid selling resource owner provided")]
     SellingResourceOwnerInvalid,
+
+    // 6032
+    #[msg("Pricing curve is invalid")]
+    InvalidPricingCurve,
+
+    // 6033
+    #[msg("Allowlist proof is not provided")]
+    AllowlistProofNotProvided,
+
+    // 6034
+    #[msg("Allowlist proof is invalid")]
+    InvalidAllowlistProof,
+
+    // 6035
+    #[msg("Presale should start before StartDate")]
+    PresaleStartDateIsInvalid,
+
+    // 6036
+    #[msg("Quantity is zero")]
+    QuantityIsZero,
+
+    // 6037
+    #[msg("Edition accounts don't match quantity")]
+    EditionAccountsMismatch,
+
+    // 6038
+    #[msg("Store still has active selling resources")]
+    StoreHasActiveSellingResources,
+
+    // 6039
+    #[msg("Store admin transfer is not proposed")]
+    PendingAdminIsNotProposed,
+
+    // 6040
+    #[msg("Platform fee basis points cannot be greater than 10000")]
+    PlatformFeeIsTooHigh,
+
+    // 6041
+    #[msg("Market is cancelled")]
+    MarketIsCancelled,
+
+    // 6042
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+
+    // 6043
+    #[msg("Funds of trade history are not refunded")]
+    FundsAreNotRefunded,
+
+    // 6044
+    #[msg("Selling resource kind doesn't match instruction")]
+    InvalidSellingResourceKind,
+
+    // 6045
+    #[msg("Fungible resource mint should have zero decimals")]
+    ResourceMintHasDecimals,
+
+    // 6046
+    #[msg("Platform fee and referral fee together are too high")]
+    ReferralFeeIsTooHigh,
+
+    // 6047
+    #[msg("Buyer can't be referrer of his own purchase")]
+    ReferrerIsBuyer,
+
+    // 6048
+    #[msg("Market is paused")]
+    MarketIsPaused,
+
+    // 6049
+    #[msg("Market is not paused")]
+    MarketIsNotPaused,
+
+    // 6050
+    #[msg("Market is already started")]
+    MarketIsStarted,
+
+    // 6051
+    #[msg("Treasury holder and treasury owner should be provided with treasury mint")]
+    TreasuryAccountsNotProvided,
+
+    // 6052
+    #[msg("Market allocation is exhausted")]
+    AllocationIsExhausted,
+
+    // 6053
+    #[msg("Allocation is gt than available supply")]
+    AllocationIsTooBig,
+
+    // 6054
+    #[msg("Market has already released the resource")]
+    MarketIsAlreadyClaimed,
+
+    // 6055
+    #[msg("Payout accounts don't match creators and market owner")]
+    PayoutAccountsMismatch,
+
+    // 6056
+    #[msg("Creators shares don't sum up to 100")]
+    CreatorsSharesIsInvalid,
+
+    // 6057
+    #[msg("Primary metadata creators are locked after the first sale")]
+    PrimaryMetadataCreatorsAreLocked,
+
+    // 6058
+    #[msg("Gate token accounts are not provided")]
+    GateAccountsNotProvided,
+
+    // 6059
+    #[msg("Gate token doesn't pass market gate")]
+    GateTokenIsInvalid,
+
+    // 6060
+    #[msg("Gate token reach buy limit")]
+    GateTokenReachBuyLimit,
+
+    // 6061
+    #[msg("Price tiers are invalid")]
+    InvalidPriceTiers,
+
+    // 6062
+    #[msg("Market can't have both price tiers and pricing curve")]
+    PriceTiersWithPricingCurve,
 }
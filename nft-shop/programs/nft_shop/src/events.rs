@@ -0,0 +1,146 @@
+//! Module provide program events
+
+use crate::state::{Gate, PriceTier};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum PayoutRole {
+    Creator,
+    MarketOwner,
+    // Funder is NFT creator and `Market` owner at the same time
+    CreatorAndMarketOwner,
+    Platform,
+    Referrer,
+}
+
+#[event]
+pub struct StoreCreated {
+    pub store: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct StoreUpdated {
+    pub store: Pubkey,
+    pub platform_fee_basis_points: u16,
+    pub platform_fee_destination: Pubkey,
+}
+
+#[event]
+pub struct StoreAdminChanged {
+    pub store: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct StoreClosed {
+    pub store: Pubkey,
+}
+
+#[event]
+pub struct SellingResourceInitialized {
+    pub store: Pubkey,
+    pub selling_resource: Pubkey,
+    pub owner: Pubkey,
+    pub resource: Pubkey,
+    pub max_supply: Option<u64>,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub store: Pubkey,
+    pub market: Pubkey,
+    pub selling_resource: Pubkey,
+    pub owner: Pubkey,
+    pub treasury_mint: Pubkey,
+    pub price: u64,
+    pub start_date: u64,
+    pub end_date: Option<u64>,
+    pub referral_basis_points: u16,
+    pub allocation: Option<u64>,
+    pub gate: Option<Gate>,
+    pub price_tiers: Vec<PriceTier>,
+}
+
+#[event]
+pub struct MarketChanged {
+    pub market: Pubkey,
+    pub mutable: bool,
+    pub price: u64,
+    pub pieces_in_one_wallet: Option<u64>,
+    pub start_date: u64,
+    pub end_date: Option<u64>,
+    pub treasury_mint: Pubkey,
+    pub price_tiers: Vec<PriceTier>,
+}
+
+#[event]
+pub struct MarketClosed {
+    pub market: Pubkey,
+}
+
+#[event]
+pub struct MarketPaused {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MarketResumed {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub end_date: Option<u64>,
+}
+
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+}
+
+#[event]
+pub struct ReferralCreated {
+    pub market: Pubkey,
+    pub referral: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct EditionBought {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    pub new_mint: Pubkey,
+    pub edition: u64,
+    pub price: u64,
+}
+
+#[event]
+pub struct FungibleBought {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    pub quantity: u64,
+    pub price: u64,
+}
+
+#[event]
+pub struct Refunded {
+    pub market: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PayoutWithdrawn {
+    pub market: Pubkey,
+    pub funder: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub role: PayoutRole,
+}
+
+#[event]
+pub struct ResourceClaimed {
+    pub market: Pubkey,
+    pub selling_resource: Pubkey,
+    pub destination: Pubkey,
+}
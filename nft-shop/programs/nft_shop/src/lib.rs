@@ -2,6 +2,7 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod error;
+pub mod events;
 pub mod pda;
 pub mod processor;
 pub mod state;
@@ -10,8 +11,8 @@ pub mod utils;
 use crate::{
     error::ErrorCode,
     state::{
-        Creator, Market, PayoutTicket, PrimaryMetadataCreators, SellingResource, Store,
-        TradeHistory,
+        Allowlist, AllowlistProof, Creator, Gate, Market, PayoutTicket, PriceTier, PricingCurve,
+        PrimaryMetadataCreators, Referral, SellingResource, Store, TradeHistory,
     },
     utils::*,
 };
@@ -35,19 +36,51 @@ pub mod nft_shop {
         ctx.accounts.process(name, description)
     }
 
+    pub fn update_store(
+        ctx: Context<UpdateStore>,
+        new_name: Option<String>,
+        new_description: Option<String>,
+        new_platform_fee_basis_points: Option<u16>,
+        new_platform_fee_destination: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            new_name,
+            new_description,
+            new_platform_fee_basis_points,
+            new_platform_fee_destination,
+        )
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn close_store(ctx: Context<CloseStore>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
     pub fn init_selling_resource(
         ctx: Context<InitSellingResource>,
-        master_edition_bump: u8,
-        vault_owner_bump: u8,
         max_supply: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts
-            .process(master_edition_bump, vault_owner_bump, max_supply)
+        let vault_owner_bump = *ctx.bumps.get("vault_owner").unwrap();
+        ctx.accounts.process(vault_owner_bump, max_supply)
+    }
+
+    pub fn init_fungible_selling_resource(
+        ctx: Context<InitFungibleSellingResource>,
+        amount: u64,
+    ) -> Result<()> {
+        let vault_owner_bump = *ctx.bumps.get("vault_owner").unwrap();
+        ctx.accounts.process(vault_owner_bump, amount)
     }
 
     pub fn create_market(
         ctx: Context<CreateMarket>,
-        _treasury_owner_bump: u8,
         name: String,
         description: String,
         mutable: bool,
@@ -55,9 +88,16 @@ pub mod nft_shop {
         pieces_in_one_wallet: Option<u64>,
         start_date: u64,
         end_date: Option<u64>,
+        pricing_curve: Option<PricingCurve>,
+        allowlist: Option<Allowlist>,
+        referral_basis_points: u16,
+        allocation: Option<u64>,
+        gate: Option<Gate>,
+        price_tiers: Vec<PriceTier>,
     ) -> Result<()> {
+        let treasury_owner_bump = *ctx.bumps.get("treasury_owner").unwrap();
         ctx.accounts.process(
-            _treasury_owner_bump,
+            treasury_owner_bump,
             name,
             description,
             mutable,
@@ -65,6 +105,12 @@ pub mod nft_shop {
             pieces_in_one_wallet,
             start_date,
             end_date,
+            pricing_curve,
+            allowlist,
+            referral_basis_points,
+            allocation,
+            gate,
+            price_tiers,
         )
     }
 
@@ -75,6 +121,10 @@ pub mod nft_shop {
         mutable: Option<bool>,
         new_price: Option<u64>,
         new_pieces_in_one_wallet: Option<u64>,
+        new_start_date: Option<u64>,
+        // `Some(None)` removes `end_date` of `Market`
+        new_end_date: Option<Option<u64>>,
+        new_price_tiers: Option<Vec<PriceTier>>,
     ) -> Result<()> {
         ctx.accounts.process(
             new_name,
@@ -82,40 +132,103 @@ pub mod nft_shop {
             mutable,
             new_price,
             new_pieces_in_one_wallet,
+            new_start_date,
+            new_end_date,
+            new_price_tiers,
         )
     }
 
     pub fn save_primary_metadata_creators(
         ctx: Context<SavePrimaryMetadataCreators>,
-        primary_metadata_creators_bump: u8,
         creators: Vec<Creator>,
+    ) -> Result<()> {
+        ctx.accounts.process(creators)
+    }
+
+    pub fn update_primary_metadata_creators(
+        ctx: Context<UpdatePrimaryMetadataCreators>,
+        creators: Vec<Creator>,
+    ) -> Result<()> {
+        ctx.accounts.process(creators)
+    }
+
+    pub fn close_primary_metadata_creators(
+        ctx: Context<ClosePrimaryMetadataCreators>,
+    ) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn create_referral(ctx: Context<CreateReferral>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn buy(ctx: Context<Buy>, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
+        ctx.accounts.process(allowlist_proof)
+    }
+
+    pub fn buy_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyMany<'info>>,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
     ) -> Result<()> {
         ctx.accounts
-            .process(primary_metadata_creators_bump, creators)
+            .process(quantity, allowlist_proof, ctx.remaining_accounts)
     }
 
-    pub fn buy(ctx: Context<Buy>, _trade_history_bump: u8, vault_owner_bump: u8) -> Result<()> {
-        ctx.accounts.process(_trade_history_bump, vault_owner_bump)
+    pub fn buy_fungible(
+        ctx: Context<BuyFungible>,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts.process(quantity, allowlist_proof)
     }
 
     pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
         ctx.accounts.process()
     }
 
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
-        treasury_owner_bump: u8,
-        payout_ticket_bump: u8,
-    ) -> Result<()> {
-        ctx.accounts.process(
-            treasury_owner_bump,
-            payout_ticket_bump,
-            ctx.remaining_accounts,
-        )
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn pause_market(ctx: Context<PauseMarket>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn resume_market(ctx: Context<ResumeMarket>, extend_end_date: bool) -> Result<()> {
+        ctx.accounts.process(extend_end_date)
+    }
+
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_all<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawAll<'info>>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_platform_fee(ctx: Context<WithdrawPlatformFee>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn withdraw_referral(ctx: Context<WithdrawReferral>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn close_trade_history(ctx: Context<CloseTradeHistory>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn close_payout_ticket(ctx: Context<ClosePayoutTicket>) -> Result<()> {
+        ctx.accounts.process()
     }
 
-    pub fn claim_resource(ctx: Context<ClaimResource>, vault_owner_bump: u8) -> Result<()> {
-        ctx.accounts.process(vault_owner_bump)
+    pub fn claim_resource(ctx: Context<ClaimResource>) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
     }
 }
 
@@ -130,9 +243,43 @@ pub struct CreateStore<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(master_edition_bump:u8, vault_owner_bump: u8, max_supply: Option<u64>)]
+#[instruction(new_name: Option<String>, new_description: Option<String>, new_platform_fee_basis_points: Option<u16>, new_platform_fee_destination: Option<Pubkey>)]
+pub struct UpdateStore<'info> {
+    #[account(mut, constraint = store.admin == store_admin.key())]
+    store: Box<Account<'info, Store>>,
+    store_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct TransferAdmin<'info> {
+    #[account(mut, constraint = store.admin == store_admin.key())]
+    store: Box<Account<'info, Store>>,
+    store_admin: Signer<'info>,
+    /// CHECK: checked in program
+    new_admin: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AcceptAdmin<'info> {
+    #[account(mut)]
+    store: Box<Account<'info, Store>>,
+    new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct CloseStore<'info> {
+    #[account(mut, close=store_admin, constraint = store.admin == store_admin.key())]
+    store: Box<Account<'info, Store>>,
+    #[account(mut)]
+    store_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
 pub struct InitSellingResource<'info> {
-    #[account(constraint = store.admin == store_admin.key())]
+    #[account(mut, constraint = store.admin == store_admin.key())]
     store: Box<Account<'info, Store>>,
     #[account(mut)]
     store_admin: Signer<'info>,
@@ -152,7 +299,34 @@ pub struct InitSellingResource<'info> {
     master_edition: UncheckedAccount<'info>,
     #[account(mut, constraint = vault.owner == vault_owner.key())]
     vault: Box<Account<'info, TokenAccount>>,
-    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), resource_mint.key().as_ref(), store.key().as_ref()], bump=vault_owner_bump)]
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), resource_mint.key().as_ref(), store.key().as_ref()], bump)]
+    /// CHECK: checked in program
+    vault_owner: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitFungibleSellingResource<'info> {
+    #[account(mut, constraint = store.admin == store_admin.key())]
+    store: Box<Account<'info, Store>>,
+    #[account(mut)]
+    store_admin: Signer<'info>,
+    #[account(init, payer=store_admin, space=SellingResource::LEN)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    /// CHECK: checked in program
+    selling_resource_owner: UncheckedAccount<'info>,
+    resource_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    resource_token: UncheckedAccount<'info>,
+    #[account(owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    metadata: UncheckedAccount<'info>,
+    #[account(mut, constraint = vault.owner == vault_owner.key(), constraint = vault.mint == resource_mint.key())]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), resource_mint.key().as_ref(), store.key().as_ref()], bump)]
     /// CHECK: checked in program
     vault_owner: UncheckedAccount<'info>,
     system_program: Program<'info, System>,
@@ -161,7 +335,6 @@ pub struct InitSellingResource<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(treasury_owner_bump: u8, name: String, description: String, mutable: bool, price: u64, pieces_in_one_wallet: Option<u64>, start_date: u64, end_date: Option<u64>)]
 pub struct CreateMarket<'info> {
     #[account(init, space=Market::LEN, payer=selling_resource_owner)]
     market: Box<Account<'info, Market>>,
@@ -175,23 +348,41 @@ pub struct CreateMarket<'info> {
     #[account(mut)]
     /// CHECK: checked in program
     treasury_holder: UncheckedAccount<'info>,
-    #[account(seeds=[HOLDER_PREFIX.as_bytes(), treasury_mint.key().as_ref(), selling_resource.key().as_ref()], bump=treasury_owner_bump)]
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), treasury_mint.key().as_ref(), selling_resource.key().as_ref(), market.key().as_ref()], bump)]
     /// CHECK: checked in program
     treasury_owner: UncheckedAccount<'info>,
     system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(new_name: Option<String>, new_description: Option<String>, mutable: Option<bool>, new_price: Option<u64>, new_pieces_in_one_wallet: Option<u64>)]
+#[instruction(new_name: Option<String>, new_description: Option<String>, mutable: Option<bool>, new_price: Option<u64>, new_pieces_in_one_wallet: Option<u64>, new_start_date: Option<u64>, new_end_date: Option<Option<u64>>, new_price_tiers: Option<Vec<PriceTier>>)]
 pub struct ChangeMarket<'info> {
     #[account(mut, constraint = market.owner == selling_resource_owner.key())]
     market: Account<'info, Market>,
+    #[account(mut)]
     selling_resource_owner: Signer<'info>,
     clock: Sysvar<'info, Clock>,
+    system_program: Program<'info, System>,
+    // Provided only to change treasury of `Market`
+    /// CHECK: checked in program
+    treasury_mint: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    treasury_owner: Option<UncheckedAccount<'info>>,
+    // Replaced treasury, its rent is returned to `selling_resource_owner`
+    #[account(mut)]
+    /// CHECK: checked in program
+    old_treasury_holder: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    old_treasury_owner: Option<UncheckedAccount<'info>>,
+    // Provided only if replaced treasury is a token account
+    /// CHECK: checked in program
+    old_treasury_token_program: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-#[instruction(primary_metadata_creators_bump: u8, creators: Vec<mpl_token_metadata::state::Creator>)]
 pub struct SavePrimaryMetadataCreators<'info> {
     #[account(mut, owner=mpl_token_metadata::id())]
     /// CHECK: checked in program
@@ -204,7 +395,37 @@ pub struct SavePrimaryMetadataCreators<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(trade_history_bump:u8, vault_owner_bump: u8)]
+pub struct UpdatePrimaryMetadataCreators<'info> {
+    #[account(owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    metadata: UncheckedAccount<'info>,
+    metadata_update_authority: Signer<'info>,
+    #[account(mut, seeds=[PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(), metadata.key.as_ref()], bump)]
+    primary_metadata_creators: Box<Account<'info, PrimaryMetadataCreators>>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePrimaryMetadataCreators<'info> {
+    #[account(owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    metadata_update_authority: Signer<'info>,
+    #[account(mut, close=metadata_update_authority, seeds=[PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(), metadata.key.as_ref()], bump)]
+    primary_metadata_creators: Box<Account<'info, PrimaryMetadataCreators>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateReferral<'info> {
+    market: Box<Account<'info, Market>>,
+    #[account(init, seeds=[REFERRAL_PREFIX.as_bytes(), market.key().as_ref(), referrer.key().as_ref()], bump, payer=referrer, space=Referral::LEN)]
+    referral: Box<Account<'info, Referral>>,
+    #[account(mut)]
+    referrer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
 pub struct Buy<'info> {
     #[account(mut, has_one=treasury_holder, has_one=selling_resource)]
     market: Box<Account<'info, Market>>,
@@ -244,7 +465,62 @@ pub struct Buy<'info> {
     edition_marker: UncheckedAccount<'info>,
     #[account(mut, constraint = vault.owner == vault_owner.key())]
     vault: Box<Account<'info, TokenAccount>>,
-    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=vault_owner_bump)]
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=selling_resource.vault_owner_bump)]
+    /// CHECK: checked in program
+    vault_owner: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    token_metadata_program: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+    clock: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    rent: Sysvar<'info, Rent>,
+    // Partner who brought the buyer, if any
+    #[account(mut, has_one=market)]
+    referral: Option<Box<Account<'info, Referral>>>,
+    // Treasury mint and its program, only for `Market`s paid in Token-2022 tokens
+    /// CHECK: checked in program
+    treasury_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    treasury_token_program: Option<UncheckedAccount<'info>>,
+    // Buyer's gate token account, gate token metadata and `GateUsage`, only for gated `Market`s
+    /// CHECK: checked in program
+    gate_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    gate_metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    gate_usage: Option<UncheckedAccount<'info>>,
+    // Creators of the primary sale saved for the resource, locked on sale if they exist
+    #[account(mut)]
+    /// CHECK: checked in program
+    primary_metadata_creators: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyMany<'info> {
+    #[account(mut, has_one=treasury_holder, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    user_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(init_if_needed, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump, payer=user_wallet, space=TradeHistory::LEN)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    metadata: UncheckedAccount<'info>,
+    #[account(mut, owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    master_edition: UncheckedAccount<'info>,
+    #[account(mut, constraint = vault.owner == vault_owner.key())]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=selling_resource.vault_owner_bump)]
     /// CHECK: checked in program
     vault_owner: UncheckedAccount<'info>,
     /// CHECK: checked in program
@@ -253,6 +529,76 @@ pub struct Buy<'info> {
     clock: Sysvar<'info, Clock>,
     token_program: Program<'info, Token>,
     rent: Sysvar<'info, Rent>,
+    // Partner who brought the buyer, if any
+    #[account(mut, has_one=market)]
+    referral: Option<Box<Account<'info, Referral>>>,
+    // Treasury mint and its program, only for `Market`s paid in Token-2022 tokens
+    /// CHECK: checked in program
+    treasury_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    treasury_token_program: Option<UncheckedAccount<'info>>,
+    // Buyer's gate token account, gate token metadata and `GateUsage`, only for gated `Market`s
+    /// CHECK: checked in program
+    gate_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    gate_metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    gate_usage: Option<UncheckedAccount<'info>>,
+    // Creators of the primary sale saved for the resource, locked on sale if they exist
+    #[account(mut)]
+    /// CHECK: checked in program
+    primary_metadata_creators: UncheckedAccount<'info>,
+    // ### Below accounts should be passed for every bought edition
+    // ### (new_metadata, new_edition, new_mint, new_token_account, edition_marker)
+}
+
+#[derive(Accounts)]
+pub struct BuyFungible<'info> {
+    #[account(mut, has_one=treasury_holder, has_one=selling_resource)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut, has_one=vault)]
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    user_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    #[account(init_if_needed, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump, payer=user_wallet, space=TradeHistory::LEN)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(mut, constraint = vault.owner == vault_owner.key())]
+    vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=selling_resource.vault_owner_bump)]
+    /// CHECK: checked in program
+    vault_owner: UncheckedAccount<'info>,
+    #[account(mut, constraint = destination.owner == user_wallet.key(), constraint = destination.mint == selling_resource.resource)]
+    destination: Box<Account<'info, TokenAccount>>,
+    system_program: Program<'info, System>,
+    clock: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    // Partner who brought the buyer, if any
+    #[account(mut, has_one=market)]
+    referral: Option<Box<Account<'info, Referral>>>,
+    // Treasury mint and its program, only for `Market`s paid in Token-2022 tokens
+    /// CHECK: checked in program
+    treasury_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    treasury_token_program: Option<UncheckedAccount<'info>>,
+    // Buyer's gate token account, gate token metadata and `GateUsage`, only for gated `Market`s
+    /// CHECK: checked in program
+    gate_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in program
+    gate_metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    gate_usage: Option<UncheckedAccount<'info>>,
+    // Creators of the primary sale saved for the resource, locked on sale if they exist
+    #[account(mut)]
+    /// CHECK: checked in program
+    primary_metadata_creators: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -265,9 +611,62 @@ pub struct CloseMarket<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(treasury_owner_bump: u8, payout_ticket_bump: u8)]
+#[instruction()]
+pub struct CancelMarket<'info> {
+    #[account(mut, constraint = market.owner == selling_resource_owner.key())]
+    market: Account<'info, Market>,
+    selling_resource_owner: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct PauseMarket<'info> {
+    #[account(mut, has_one=store)]
+    market: Account<'info, Market>,
+    store: Box<Account<'info, Store>>,
+    #[account(constraint = market.owner == authority.key() || store.admin == authority.key())]
+    authority: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction(extend_end_date: bool)]
+pub struct ResumeMarket<'info> {
+    #[account(mut, has_one=store)]
+    market: Account<'info, Market>,
+    store: Box<Account<'info, Store>>,
+    #[account(constraint = market.owner == authority.key() || store.admin == authority.key())]
+    authority: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut, has_one=treasury_holder, has_one=treasury_mint)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    user_wallet: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    treasury_mint: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref(), market.key().as_ref()], bump=market.treasury_owner_bump)]
+    /// CHECK: checked in program
+    treasury_owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    destination: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    token_program: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
 pub struct Withdraw<'info> {
-    #[account(has_one=treasury_holder, has_one=selling_resource, has_one=treasury_mint)]
+    #[account(mut, has_one=treasury_holder, has_one=selling_resource, has_one=treasury_mint)]
     market: Box<Account<'info, Market>>,
     selling_resource: Box<Account<'info, SellingResource>>,
     #[account(owner=mpl_token_metadata::id())]
@@ -278,7 +677,7 @@ pub struct Withdraw<'info> {
     treasury_holder: UncheckedAccount<'info>,
     /// CHECK: checked in program
     treasury_mint: UncheckedAccount<'info>,
-    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref()], bump=treasury_owner_bump)]
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref(), market.key().as_ref()], bump=market.treasury_owner_bump)]
     /// CHECK: checked in program
     treasury_owner: UncheckedAccount<'info>,
     #[account(mut)]
@@ -292,7 +691,8 @@ pub struct Withdraw<'info> {
     payout_ticket: Box<Account<'info, PayoutTicket>>,
     rent: Sysvar<'info, Rent>,
     clock: Sysvar<'info, Clock>,
-    token_program: Program<'info, Token>,
+    /// CHECK: checked in program
+    token_program: UncheckedAccount<'info>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
     // ### Below account is optional and should be passed only during primary sale
@@ -301,11 +701,129 @@ pub struct Withdraw<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(vault_owner_bump: u8)]
+pub struct WithdrawAll<'info> {
+    #[account(mut, has_one=treasury_holder, has_one=selling_resource, has_one=treasury_mint)]
+    market: Box<Account<'info, Market>>,
+    selling_resource: Box<Account<'info, SellingResource>>,
+    #[account(owner=mpl_token_metadata::id())]
+    /// CHECK: checked in program
+    metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    treasury_mint: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref(), market.key().as_ref()], bump=market.treasury_owner_bump)]
+    /// CHECK: checked in program
+    treasury_owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    selling_resource_owner: Signer<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+    /// CHECK: checked in program
+    token_program: UncheckedAccount<'info>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+    // ### Below account is optional and should be passed only during primary sale
+    // ### List of creators who should receive royalties from primary sale
+    // primary_metadata_creators_data: Account<'info, PrimaryMetadataCreators>,
+    // ### Then for every creator in order, and `Market` owner last after primary sale
+    // funder: UncheckedAccount<'info>,
+    // destination: UncheckedAccount<'info>,
+    // payout_ticket: Account<'info, PayoutTicket>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPlatformFee<'info> {
+    #[account(mut, has_one=store, has_one=treasury_holder, has_one=treasury_mint, has_one=platform_fee_destination)]
+    market: Box<Account<'info, Market>>,
+    #[account(constraint = store.admin == store_admin.key())]
+    store: Box<Account<'info, Store>>,
+    #[account(mut)]
+    store_admin: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    treasury_mint: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref(), market.key().as_ref()], bump=market.treasury_owner_bump)]
+    /// CHECK: checked in program
+    treasury_owner: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    platform_fee_destination: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    destination: UncheckedAccount<'info>,
+    #[account(init_if_needed, seeds=[PLATFORM_FEE_TICKET_PREFIX.as_bytes(), market.key().as_ref()], bump, payer=store_admin, space=PayoutTicket::LEN)]
+    payout_ticket: Box<Account<'info, PayoutTicket>>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+    /// CHECK: checked in program
+    token_program: UncheckedAccount<'info>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReferral<'info> {
+    #[account(mut, has_one=treasury_holder, has_one=treasury_mint)]
+    market: Box<Account<'info, Market>>,
+    #[account(mut, has_one=market, has_one=referrer)]
+    referral: Box<Account<'info, Referral>>,
+    #[account(mut)]
+    referrer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    /// CHECK: checked in program
+    treasury_mint: UncheckedAccount<'info>,
+    #[account(seeds=[HOLDER_PREFIX.as_bytes(), market.treasury_mint.as_ref(), market.selling_resource.as_ref(), market.key().as_ref()], bump=market.treasury_owner_bump)]
+    /// CHECK: checked in program
+    treasury_owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in program
+    destination: UncheckedAccount<'info>,
+    #[account(init_if_needed, seeds=[REFERRAL_TICKET_PREFIX.as_bytes(), market.key().as_ref(), referrer.key().as_ref()], bump, payer=referrer, space=PayoutTicket::LEN)]
+    payout_ticket: Box<Account<'info, PayoutTicket>>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+    /// CHECK: checked in program
+    token_program: UncheckedAccount<'info>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTradeHistory<'info> {
+    market: Box<Account<'info, Market>>,
+    #[account(mut, close=user_wallet, seeds=[HISTORY_PREFIX.as_bytes(), user_wallet.key().as_ref(), market.key().as_ref()], bump)]
+    trade_history: Box<Account<'info, TradeHistory>>,
+    #[account(mut)]
+    user_wallet: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ClosePayoutTicket<'info> {
+    #[account(has_one=treasury_holder)]
+    market: Box<Account<'info, Market>>,
+    /// CHECK: checked in program
+    treasury_holder: UncheckedAccount<'info>,
+    #[account(mut, close=payer, has_one=market, has_one=payer)]
+    payout_ticket: Box<Account<'info, PayoutTicket>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
 pub struct ClaimResource<'info> {
-    #[account(has_one=selling_resource, has_one=treasury_holder)]
+    #[account(mut, has_one=selling_resource, has_one=treasury_holder)]
     market: Account<'info, Market>,
-    #[account(has_one=vault, constraint = selling_resource.owner == selling_resource_owner.key())]
+    #[account(mut)]
+    store: Box<Account<'info, Store>>,
+    #[account(mut, has_one=vault, has_one=store, constraint = selling_resource.owner == selling_resource_owner.key())]
     selling_resource: Account<'info, SellingResource>,
     selling_resource_owner: Signer<'info>,
     #[account(mut, owner=mpl_token_metadata::id())]
@@ -315,7 +833,7 @@ pub struct ClaimResource<'info> {
     treasury_holder: UncheckedAccount<'info>,
     #[account(mut, constraint = vault.owner == vault_owner.key())]
     vault: Box<Account<'info, TokenAccount>>,
-    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=vault_owner_bump)]
+    #[account(seeds=[VAULT_OWNER_PREFIX.as_bytes(), selling_resource.resource.as_ref(), selling_resource.store.as_ref()], bump=selling_resource.vault_owner_bump)]
     /// CHECK: checked in program
     vault_owner: UncheckedAccount<'info>,
     #[account(mut)]
@@ -2,8 +2,9 @@ use anchor_lang::prelude::Pubkey;
 
 use crate::id;
 use crate::utils::{
-    HISTORY_PREFIX, HOLDER_PREFIX, PAYOUT_TICKET_PREFIX, PRIMARY_METADATA_CREATORS_PREFIX,
-    VAULT_OWNER_PREFIX,
+    GATE_USAGE_PREFIX, HISTORY_PREFIX, HOLDER_PREFIX, PAYOUT_TICKET_PREFIX,
+    PLATFORM_FEE_TICKET_PREFIX, PRIMARY_METADATA_CREATORS_PREFIX, REFERRAL_PREFIX,
+    REFERRAL_TICKET_PREFIX, VAULT_OWNER_PREFIX,
 };
 
 pub fn find_vault_owner_address(resource_mint: &Pubkey, store: &Pubkey) -> (Pubkey, u8) {
@@ -20,12 +21,14 @@ pub fn find_vault_owner_address(resource_mint: &Pubkey, store: &Pubkey) -> (Pubk
 pub fn find_treasury_owner_address(
     treasury_mint: &Pubkey,
     selling_resource: &Pubkey,
+    market: &Pubkey,
 ) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             HOLDER_PREFIX.as_bytes(),
             treasury_mint.as_ref(),
             selling_resource.as_ref(),
+            market.as_ref(),
         ],
         &id(),
     )
@@ -58,3 +61,43 @@ pub fn find_payout_ticket_address(market: &Pubkey, funder: &Pubkey) -> (Pubkey,
         &id(),
     )
 }
+
+pub fn find_platform_fee_ticket_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PLATFORM_FEE_TICKET_PREFIX.as_bytes(), market.as_ref()],
+        &id(),
+    )
+}
+
+pub fn find_referral_address(market: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERRAL_PREFIX.as_bytes(),
+            market.as_ref(),
+            referrer.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_referral_ticket_address(market: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERRAL_TICKET_PREFIX.as_bytes(),
+            market.as_ref(),
+            referrer.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_gate_usage_address(market: &Pubkey, gate_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            GATE_USAGE_PREFIX.as_bytes(),
+            market.as_ref(),
+            gate_mint.as_ref(),
+        ],
+        &id(),
+    )
+}
@@ -0,0 +1,25 @@
+use crate::{error::ErrorCode, events::StoreAdminChanged, AcceptAdmin};
+use anchor_lang::prelude::*;
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let store = &mut self.store;
+        let new_admin = &self.new_admin;
+
+        // Check, that `new_admin` was proposed by current admin
+        if store.pending_admin != Some(new_admin.key()) {
+            return Err(ErrorCode::PendingAdminIsNotProposed.into());
+        }
+
+        emit!(StoreAdminChanged {
+            store: store.key(),
+            old_admin: store.admin,
+            new_admin: new_admin.key(),
+        });
+
+        store.admin = new_admin.key();
+        store.pending_admin = None;
+
+        Ok(())
+    }
+}
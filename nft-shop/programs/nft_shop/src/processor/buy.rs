@@ -1,20 +1,15 @@
 use crate::{
     error::ErrorCode,
-    state::{MarketState, SellingResourceState},
+    events::EditionBought,
+    state::{AllowlistProof, SellingResourceKind},
     utils::*,
     Buy,
 };
 use anchor_lang::prelude::*;
-use anchor_lang::{
-    solana_program::{program::invoke, system_instruction},
-    system_program::System,
-};
-use anchor_spl::token;
 use mpl_token_metadata::utils::get_supply_off_master_edition;
-use std::cmp::Ordering;
 
 impl<'info> Buy<'info> {
-    pub fn process(&mut self, _trade_history_bump: u8, vault_owner_bump: u8) -> Result<()> {
+    pub fn process(&mut self, allowlist_proof: Option<AllowlistProof>) -> Result<()> {
         let market = &mut self.market;
         let selling_resource = &mut self.selling_resource;
         let user_token_account = Box::new(&self.user_token_account);
@@ -34,6 +29,23 @@ impl<'info> Buy<'info> {
         let rent = &self.rent;
         let token_program = &self.token_program;
         let system_program = &self.system_program;
+        let referral = &mut self.referral;
+        let treasury_mint = self.treasury_mint.as_ref().map(|a| a.to_account_info());
+        let treasury_token_program = self
+            .treasury_token_program
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_token_account = self
+            .gate_token_account
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_metadata = self.gate_metadata.as_ref().map(|a| a.to_account_info());
+        let gate_usage = self.gate_usage.as_ref().map(|a| a.to_account_info());
+        let primary_metadata_creators = &self.primary_metadata_creators;
+
+        if selling_resource.kind != SellingResourceKind::MasterEdition {
+            return Err(ErrorCode::InvalidSellingResourceKind.into());
+        }
 
         let metadata_mint = selling_resource.resource;
         // Do supply +1 to increase master edition supply
@@ -41,19 +53,9 @@ impl<'info> Buy<'info> {
             .checked_add(1)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Check, that `Market` is started
-        if market.start_date > clock.unix_timestamp as u64 {
-            return Err(ErrorCode::MarketIsNotStarted.into());
-        }
+        let now = clock.unix_timestamp as u64;
 
-        // Check, that `Market` is ended
-        if let Some(end_date) = market.end_date {
-            if clock.unix_timestamp as u64 > end_date {
-                return Err(ErrorCode::MarketIsEnded.into());
-            }
-        } else if market.state == MarketState::Ended {
-            return Err(ErrorCode::MarketIsEnded.into());
-        }
+        assert_market_is_live(market, now)?;
 
         if trade_history.market != market.key() {
             trade_history.market = market.key();
@@ -63,54 +65,45 @@ impl<'info> Buy<'info> {
             trade_history.wallet = user_wallet.key();
         }
 
-        // Check, that user not reach buy limit
-        if let Some(pieces_in_one_wallet) = market.pieces_in_one_wallet {
-            if trade_history.already_bought == pieces_in_one_wallet {
-                return Err(ErrorCode::UserReachBuyLimit.into());
-            }
-        }
+        assert_buy_limits(
+            market,
+            trade_history,
+            &user_wallet.key(),
+            1,
+            allowlist_proof,
+            now,
+        )?;
 
-        // Buy new edition
-        let is_native = market.treasury_mint == System::id();
-
-        if !is_native {
-            let cpi_program = token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: user_token_account.to_account_info(),
-                to: treasury_holder.to_account_info(),
-                authority: user_wallet.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, market.price)?;
-        } else {
-            if user_token_account.key() != user_wallet.key() {
-                return Err(ErrorCode::UserWalletMustMatchUserTokenAccount.into());
-            }
-
-            invoke(
-                // For native SOL transfer user_wallet key == user_token_account key
-                &system_instruction::transfer(
-                    &user_token_account.key(),
-                    &treasury_holder.key(),
-                    market.price,
-                ),
-                &[
-                    user_token_account.to_account_info(),
-                    treasury_holder.to_account_info(),
-                ],
-            )?;
-        }
+        assert_gate(
+            market,
+            &market.key(),
+            &user_wallet.to_account_info(),
+            gate_token_account.as_ref(),
+            gate_metadata.as_ref(),
+            gate_usage.as_ref(),
+            &system_program.to_account_info(),
+            1,
+        )?;
 
-        market.funds_collected = market
-            .funds_collected
-            .checked_add(market.price)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let price = calculate_market_price(market, selling_resource.supply, now)?;
+
+        // Buy new edition
+        let received = transfer_to_treasury(
+            market,
+            &user_token_account.to_account_info(),
+            &user_wallet.to_account_info(),
+            &treasury_holder.to_account_info(),
+            &token_program.to_account_info(),
+            treasury_mint.as_ref(),
+            treasury_token_program.as_ref(),
+            price,
+        )?;
 
         let signer_seeds: &[&[&[u8]]] = &[&[
             VAULT_OWNER_PREFIX.as_bytes(),
             selling_resource.resource.as_ref(),
             selling_resource.store.as_ref(),
-            &[vault_owner_bump],
+            &[selling_resource.vault_owner_bump],
         ]];
 
         mpl_mint_new_edition_from_master_edition_via_token(
@@ -139,28 +132,27 @@ impl<'info> Buy<'info> {
             signer_seeds[0],
         )?;
 
-        trade_history.already_bought = trade_history
-            .already_bought
-            .checked_add(1)
-            .ok_or(ErrorCode::MathOverflow)?;
+        record_sale(market, selling_resource, trade_history, 1, received)?;
 
-        selling_resource.supply = selling_resource
-            .supply
-            .checked_add(1)
-            .ok_or(ErrorCode::MathOverflow)?;
+        lock_primary_metadata_creators(
+            &primary_metadata_creators.to_account_info(),
+            &selling_resource.resource,
+            &user_wallet.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
 
-        // Check, that `SellingResource::max_supply` is not overflowed by `supply`
-        if let Some(max_supply) = selling_resource.max_supply {
-            match selling_resource.supply.cmp(&max_supply) {
-                Ordering::Greater => return Err(ErrorCode::SupplyIsGtThanMaxSupply.into()),
-                Ordering::Equal => {
-                    selling_resource.state = SellingResourceState::Exhausted;
-                    market.state = MarketState::Ended;
-                }
-                Ordering::Less => (),
-            }
+        if let Some(referral) = referral {
+            accrue_referral(market, referral, &user_wallet.key(), received)?;
         }
 
+        emit!(EditionBought {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            new_mint: new_mint.key(),
+            edition,
+            price,
+        });
+
         Ok(())
     }
 }
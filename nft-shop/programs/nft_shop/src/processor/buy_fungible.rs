@@ -0,0 +1,136 @@
+use crate::{
+    error::ErrorCode,
+    events::FungibleBought,
+    state::{AllowlistProof, SellingResourceKind},
+    utils::*,
+    BuyFungible,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+impl<'info> BuyFungible<'info> {
+    pub fn process(
+        &mut self,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
+    ) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
+        let user_token_account = Box::new(&self.user_token_account);
+        let user_wallet = &mut self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let vault = &self.vault;
+        let vault_owner = &self.vault_owner;
+        let destination = &self.destination;
+        let clock = &self.clock;
+        let token_program = &self.token_program;
+        let referral = &mut self.referral;
+        let treasury_mint = self.treasury_mint.as_ref().map(|a| a.to_account_info());
+        let treasury_token_program = self
+            .treasury_token_program
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_token_account = self
+            .gate_token_account
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_metadata = self.gate_metadata.as_ref().map(|a| a.to_account_info());
+        let gate_usage = self.gate_usage.as_ref().map(|a| a.to_account_info());
+        let primary_metadata_creators = &self.primary_metadata_creators;
+        let system_program = &self.system_program;
+
+        if selling_resource.kind != SellingResourceKind::Fungible {
+            return Err(ErrorCode::InvalidSellingResourceKind.into());
+        }
+
+        if quantity == 0 {
+            return Err(ErrorCode::QuantityIsZero.into());
+        }
+
+        let now = clock.unix_timestamp as u64;
+
+        assert_market_is_live(market, now)?;
+
+        if trade_history.market != market.key() {
+            trade_history.market = market.key();
+        }
+
+        if trade_history.wallet != user_wallet.key() {
+            trade_history.wallet = user_wallet.key();
+        }
+
+        assert_buy_limits(
+            market,
+            trade_history,
+            &user_wallet.key(),
+            quantity,
+            allowlist_proof,
+            now,
+        )?;
+
+        assert_gate(
+            market,
+            &market.key(),
+            &user_wallet.to_account_info(),
+            gate_token_account.as_ref(),
+            gate_metadata.as_ref(),
+            gate_usage.as_ref(),
+            &system_program.to_account_info(),
+            quantity,
+        )?;
+
+        let price = calculate_market_price(market, selling_resource.supply, now)?;
+        let amount = calculate_market_amount(market, selling_resource.supply, quantity, now)?;
+
+        let received = transfer_to_treasury(
+            market,
+            &user_token_account.to_account_info(),
+            &user_wallet.to_account_info(),
+            &treasury_holder.to_account_info(),
+            &token_program.to_account_info(),
+            treasury_mint.as_ref(),
+            treasury_token_program.as_ref(),
+            amount,
+        )?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_OWNER_PREFIX.as_bytes(),
+            selling_resource.resource.as_ref(),
+            selling_resource.store.as_ref(),
+            &[selling_resource.vault_owner_bump],
+        ]];
+
+        // Give units from the vault stock
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = token::Transfer {
+            from: vault.to_account_info(),
+            to: destination.to_account_info(),
+            authority: vault_owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, quantity)?;
+
+        record_sale(market, selling_resource, trade_history, quantity, received)?;
+
+        lock_primary_metadata_creators(
+            &primary_metadata_creators.to_account_info(),
+            &selling_resource.resource,
+            &user_wallet.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+
+        if let Some(referral) = referral {
+            accrue_referral(market, referral, &user_wallet.key(), received)?;
+        }
+
+        emit!(FungibleBought {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            quantity,
+            price,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    error::ErrorCode,
+    events::EditionBought,
+    state::{AllowlistProof, SellingResourceKind},
+    utils::*,
+    BuyMany,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use mpl_token_metadata::utils::get_supply_off_master_edition;
+
+impl<'info> BuyMany<'info> {
+    pub fn process(
+        &mut self,
+        quantity: u64,
+        allowlist_proof: Option<AllowlistProof>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let market = &mut self.market;
+        let selling_resource = &mut self.selling_resource;
+        let user_token_account = Box::new(&self.user_token_account);
+        let user_wallet = &mut self.user_wallet;
+        let trade_history = &mut self.trade_history;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let master_edition = Box::new(&self.master_edition);
+        let vault = &mut self.vault;
+        let vault_owner = Box::new(&self.vault_owner);
+        let master_edition_metadata = Box::new(&self.metadata);
+        let clock = &self.clock;
+        let rent = &self.rent;
+        let token_program = &self.token_program;
+        let system_program = &self.system_program;
+        let referral = &mut self.referral;
+        let treasury_mint = self.treasury_mint.as_ref().map(|a| a.to_account_info());
+        let treasury_token_program = self
+            .treasury_token_program
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_token_account = self
+            .gate_token_account
+            .as_ref()
+            .map(|a| a.to_account_info());
+        let gate_metadata = self.gate_metadata.as_ref().map(|a| a.to_account_info());
+        let gate_usage = self.gate_usage.as_ref().map(|a| a.to_account_info());
+        let primary_metadata_creators = &self.primary_metadata_creators;
+
+        if selling_resource.kind != SellingResourceKind::MasterEdition {
+            return Err(ErrorCode::InvalidSellingResourceKind.into());
+        }
+
+        if quantity == 0 {
+            return Err(ErrorCode::QuantityIsZero.into());
+        }
+
+        // Every edition comes with its own group of new accounts
+        if remaining_accounts.len() as u64
+            != quantity
+                .checked_mul(EDITION_ACCOUNTS_LEN as u64)
+                .ok_or(ErrorCode::MathOverflow)?
+        {
+            return Err(ErrorCode::EditionAccountsMismatch.into());
+        }
+
+        let metadata_mint = selling_resource.resource;
+        let mut edition = get_supply_off_master_edition(&master_edition.to_account_info())?;
+
+        let now = clock.unix_timestamp as u64;
+
+        assert_market_is_live(market, now)?;
+
+        if trade_history.market != market.key() {
+            trade_history.market = market.key();
+        }
+
+        if trade_history.wallet != user_wallet.key() {
+            trade_history.wallet = user_wallet.key();
+        }
+
+        assert_buy_limits(
+            market,
+            trade_history,
+            &user_wallet.key(),
+            quantity,
+            allowlist_proof,
+            now,
+        )?;
+
+        assert_gate(
+            market,
+            &market.key(),
+            &user_wallet.to_account_info(),
+            gate_token_account.as_ref(),
+            gate_metadata.as_ref(),
+            gate_usage.as_ref(),
+            &system_program.to_account_info(),
+            quantity,
+        )?;
+
+        let amount = calculate_market_amount(market, selling_resource.supply, quantity, now)?;
+
+        // Pay for all editions at once
+        let received = transfer_to_treasury(
+            market,
+            &user_token_account.to_account_info(),
+            &user_wallet.to_account_info(),
+            &treasury_holder.to_account_info(),
+            &token_program.to_account_info(),
+            treasury_mint.as_ref(),
+            treasury_token_program.as_ref(),
+            amount,
+        )?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_OWNER_PREFIX.as_bytes(),
+            selling_resource.resource.as_ref(),
+            selling_resource.store.as_ref(),
+            &[selling_resource.vault_owner_bump],
+        ]];
+
+        // ### Accounts of each edition:
+        //
+        //   0. `[w]` New Metadata
+        //   1. `[w]` New Edition
+        //   2. `[w]` New Mint
+        //   3. `[]` New Token Account
+        //   4. `[w]` Edition Marker
+        //
+        let mut supply = selling_resource.supply;
+
+        for edition_accounts in remaining_accounts.chunks(EDITION_ACCOUNTS_LEN) {
+            let new_metadata = &edition_accounts[0];
+            let new_edition = &edition_accounts[1];
+            let new_mint = &edition_accounts[2];
+            let new_token_account = &edition_accounts[3];
+            let edition_marker = &edition_accounts[4];
+
+            // Check, that new token account belongs to the user
+            let new_token_account_data = Account::<TokenAccount>::try_from(new_token_account)?;
+            assert_keys_equal(new_token_account_data.owner, user_wallet.key())?;
+            assert_keys_equal(new_token_account_data.mint, new_mint.key())?;
+
+            // Do supply +1 to increase master edition supply
+            edition = edition.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+            mpl_mint_new_edition_from_master_edition_via_token(
+                new_metadata,
+                new_edition,
+                new_mint,
+                &user_wallet.to_account_info(),
+                &user_wallet.to_account_info(),
+                &vault_owner.to_account_info(),
+                &vault.to_account_info(),
+                &master_edition_metadata.to_account_info(),
+                &master_edition.to_account_info(),
+                &metadata_mint,
+                edition_marker,
+                &token_program.to_account_info(),
+                &system_program.to_account_info(),
+                &rent.to_account_info(),
+                edition,
+                signer_seeds[0],
+            )?;
+
+            mpl_update_primary_sale_happened_via_token(
+                new_metadata,
+                &user_wallet.to_account_info(),
+                new_token_account,
+                signer_seeds[0],
+            )?;
+
+            let price = calculate_market_price(market, supply, now)?;
+            supply = supply.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+            emit!(EditionBought {
+                market: market.key(),
+                buyer: user_wallet.key(),
+                new_mint: new_mint.key(),
+                edition,
+                price,
+            });
+        }
+
+        record_sale(market, selling_resource, trade_history, quantity, received)?;
+
+        lock_primary_metadata_creators(
+            &primary_metadata_creators.to_account_info(),
+            &selling_resource.resource,
+            &user_wallet.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+
+        if let Some(referral) = referral {
+            accrue_referral(market, referral, &user_wallet.key(), received)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{error::ErrorCode, events::MarketCancelled, state::MarketState, CancelMarket};
+use anchor_lang::prelude::*;
+
+impl<'info> CancelMarket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let clock = &self.clock;
+
+        // Check, that `Market` is not `Ended`, so nobody has withdrawn funds yet
+        if market.state != MarketState::Created && market.state != MarketState::Paused {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        }
+
+        market.state = MarketState::Cancelled;
+        market.cancelled_at = Some(clock.unix_timestamp as u64);
+
+        emit!(MarketCancelled {
+            market: market.key(),
+        });
+
+        Ok(())
+    }
+}
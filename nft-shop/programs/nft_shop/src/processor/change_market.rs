@@ -1,4 +1,7 @@
-use crate::{error::ErrorCode, utils::*, ChangeMarket};
+use crate::{
+    error::ErrorCode, events::MarketChanged, pda::find_treasury_owner_address, state::PriceTier,
+    utils::*, ChangeMarket,
+};
 use anchor_lang::prelude::*;
 
 impl<'info> ChangeMarket<'info> {
@@ -9,18 +12,31 @@ impl<'info> ChangeMarket<'info> {
         mutable: Option<bool>,
         new_price: Option<u64>,
         new_pieces_in_one_wallet: Option<u64>,
+        new_start_date: Option<u64>,
+        new_end_date: Option<Option<u64>>,
+        new_price_tiers: Option<Vec<PriceTier>>,
     ) -> Result<()> {
         let market = &mut self.market;
+        let selling_resource_owner = &self.selling_resource_owner;
+        let treasury_mint = &self.treasury_mint;
+        let treasury_holder = &self.treasury_holder;
+        let treasury_owner = &self.treasury_owner;
+        let old_treasury_holder = &self.old_treasury_holder;
+        let old_treasury_owner = &self.old_treasury_owner;
+        let old_treasury_token_program = &self.old_treasury_token_program;
         let clock = &self.clock;
 
+        let now = clock.unix_timestamp as u64;
+
         // Check, that `Market` is not in `Ended` state
         if let Some(end_date) = market.end_date {
-            if clock.unix_timestamp as u64 > end_date {
+            if now > end_date {
                 return Err(ErrorCode::MarketIsEnded.into());
             }
         }
 
         // Check, that `Market` is mutable
+        // Immutable `Market` is frozen as a whole, including its dates and treasury
         if !market.mutable {
             return Err(ErrorCode::MarketIsImmutable.into());
         }
@@ -50,14 +66,115 @@ impl<'info> ChangeMarket<'info> {
                 return Err(ErrorCode::PriceIsZero.into());
             }
 
+            // Start price of a Dutch auction cannot be under its floor
+            if let Some(pricing_curve) = &market.pricing_curve {
+                if new_price < pricing_curve.floor_price {
+                    return Err(ErrorCode::InvalidPricingCurve.into());
+                }
+            }
+
             market.price = new_price;
         }
 
+        if let Some(new_price_tiers) = new_price_tiers {
+            assert_price_tiers(&new_price_tiers, &market.pricing_curve)?;
+
+            market.price_tiers = new_price_tiers;
+        }
+
         // Check is required, because we can overwrite existing value
         if let Some(new_pieces_in_one_wallet) = new_pieces_in_one_wallet {
             market.pieces_in_one_wallet = Some(new_pieces_in_one_wallet);
         }
 
+        // Dates and treasury can be changed only until the first sale
+        if new_start_date.is_some() || new_end_date.is_some() || treasury_mint.is_some() {
+            if now >= market.start_date || market.funds_collected > 0 {
+                return Err(ErrorCode::MarketIsStarted.into());
+            }
+        }
+
+        if new_start_date.is_some() || new_end_date.is_some() {
+            let start_date = new_start_date.unwrap_or(market.start_date);
+            let end_date = new_end_date.unwrap_or(market.end_date);
+
+            assert_market_dates(start_date, end_date, now)?;
+
+            // Presale should still end when the public sale starts
+            if let Some(allowlist) = &market.allowlist {
+                if let Some(presale_start_date) = allowlist.presale_start_date {
+                    if presale_start_date >= start_date {
+                        return Err(ErrorCode::PresaleStartDateIsInvalid.into());
+                    }
+                }
+            }
+
+            market.start_date = start_date;
+            market.end_date = end_date;
+        }
+
+        if let Some(treasury_mint) = treasury_mint {
+            let treasury_holder = treasury_holder
+                .as_ref()
+                .ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+            let treasury_owner = treasury_owner
+                .as_ref()
+                .ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+
+            // Treasury owner is derived from the new treasury mint
+            let (treasury_owner_key, treasury_owner_bump) = find_treasury_owner_address(
+                &treasury_mint.key(),
+                &market.selling_resource,
+                &market.key(),
+            );
+            assert_keys_equal(treasury_owner.key(), treasury_owner_key)?;
+
+            // Old treasury is left only if it's the same account as the new one
+            if treasury_holder.key() != market.treasury_holder {
+                let old_treasury_holder = old_treasury_holder
+                    .as_ref()
+                    .ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+                let old_treasury_owner = old_treasury_owner
+                    .as_ref()
+                    .ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+                let old_treasury_token_program = old_treasury_token_program
+                    .as_ref()
+                    .map(|token_program| token_program.to_account_info());
+
+                close_treasury(
+                    market,
+                    &market.key(),
+                    &old_treasury_holder.to_account_info(),
+                    &old_treasury_owner.to_account_info(),
+                    old_treasury_token_program.as_ref(),
+                    &selling_resource_owner.to_account_info(),
+                )?;
+            }
+
+            init_treasury(
+                &treasury_mint.to_account_info(),
+                &treasury_holder.to_account_info(),
+                &treasury_owner.to_account_info(),
+                &selling_resource_owner.to_account_info(),
+            )?;
+
+            market.treasury_mint = treasury_mint.key();
+            market.treasury_holder = treasury_holder.key();
+            market.treasury_owner = treasury_owner.key();
+            market.treasury_owner_bump = treasury_owner_bump;
+        }
+
+        emit!(MarketChanged {
+            market: market.key(),
+            mutable: market.mutable,
+            price: market.price,
+            pieces_in_one_wallet: market.pieces_in_one_wallet,
+            start_date: market.start_date,
+            end_date: market.end_date,
+            treasury_mint: market.treasury_mint,
+            price_tiers: market.price_tiers.clone(),
+        });
+
         Ok(())
     }
 }
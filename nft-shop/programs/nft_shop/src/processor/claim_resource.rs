@@ -1,55 +1,26 @@
 use crate::{
     error::ErrorCode,
-    state::{MarketState, MINIMUM_BALANCE_FOR_SYSTEM_ACCS},
+    events::ResourceClaimed,
+    state::{MarketState, SellingResourceKind, SellingResourceState},
     utils::*,
     ClaimResource,
 };
-use anchor_lang::{prelude::*, solana_program::program_pack::Pack, system_program::System};
+use anchor_lang::prelude::*;
 use anchor_spl::token;
 use mpl_token_metadata::state::TokenMetadataAccount;
 
 impl<'info> ClaimResource<'info> {
-    pub fn process(&mut self, vault_owner_bump: u8) -> Result<()> {
-        let market = &self.market;
-        let selling_resource = &self.selling_resource;
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        let market = &mut self.market;
+        let store = &mut self.store;
+        let selling_resource = &mut self.selling_resource;
         let vault = &self.vault;
         let metadata = &self.metadata;
         let vault_owner = &self.vault_owner;
         let destination = &self.destination;
         let clock = &self.clock;
-        let treasury_holder = &self.treasury_holder;
         let token_program = &self.token_program;
 
-        // Check, that `Market` is `Ended`
-        if let Some(end_date) = market.end_date {
-            if clock.unix_timestamp as u64 <= end_date {
-                return Err(ErrorCode::MarketInInvalidState.into());
-            }
-        } else if market.state != MarketState::Ended {
-            return Err(ErrorCode::MarketInInvalidState.into());
-        }
-
-        let is_native = market.treasury_mint == System::id();
-
-        let treasury_holder_amount = if is_native {
-            treasury_holder
-                .lamports()
-                .checked_sub(MINIMUM_BALANCE_FOR_SYSTEM_ACCS)
-                .ok_or(ErrorCode::MathOverflow)?
-        } else {
-            let token_account = spl_token::state::Account::unpack(&treasury_holder.data.borrow())?;
-            if token_account.owner != market.treasury_owner {
-                return Err(ErrorCode::DerivedKeyInvalid.into());
-            }
-
-            token_account.amount
-        };
-
-        // Check, that treasury balance is zero
-        if treasury_holder_amount != 0 {
-            return Err(ErrorCode::TreasuryIsNotEmpty.into());
-        }
-
         // Check, that provided metadata is correct
         assert_derivation(
             &mpl_token_metadata::id(),
@@ -61,17 +32,67 @@ impl<'info> ClaimResource<'info> {
             ],
         )?;
 
+        let metadata_key = metadata.key();
+        let metadata_state: mpl_token_metadata::state::Metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
+
+        // Check, that `Market` is `Ended`
+        // `Cancelled` one can be claimed as soon as all buyers got their refunds or `REFUND_PERIOD` is over
+        if market.state != MarketState::Cancelled {
+            assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+
+            // Check, that nothing is owed from treasury, rounding remainder of the shares can be left
+            if market.funds_collected > 0 {
+                let creators =
+                    payout_creators(&metadata_key, &metadata_state, remaining_accounts.first())?;
+
+                if calculate_owed_funds(market, &metadata_state, &creators)? > 0 {
+                    return Err(ErrorCode::TreasuryIsNotEmpty.into());
+                }
+            }
+        } else {
+            let unrefunded = market
+                .funds_collected
+                .checked_sub(market.funds_refunded)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let refund_period_end = market
+                .cancelled_at
+                .unwrap_or_default()
+                .checked_add(REFUND_PERIOD)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            if unrefunded > 0 && (clock.unix_timestamp as u64) < refund_period_end {
+                return Err(ErrorCode::FundsAreNotRefunded.into());
+            }
+        }
+
+        if market.claimed {
+            return Err(ErrorCode::MarketIsAlreadyClaimed.into());
+        }
+
+        market.claimed = true;
+        selling_resource.markets = selling_resource
+            .markets
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Resource stays in the vault while other `Market`s still sell it
+        if selling_resource.markets > 0 {
+            return Ok(());
+        }
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             VAULT_OWNER_PREFIX.as_bytes(),
             selling_resource.resource.as_ref(),
             selling_resource.store.as_ref(),
-            &[vault_owner_bump],
+            &[selling_resource.vault_owner_bump],
         ]];
 
         // Update primary sale flag
-        let metadata_state: mpl_token_metadata::state::Metadata =
-            mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
-        if !metadata_state.primary_sale_happened {
+        // Fungible stock can be sold out, so its flag is left for the next sale
+        if !metadata_state.primary_sale_happened
+            && selling_resource.kind == SellingResourceKind::MasterEdition
+        {
             mpl_update_primary_sale_happened_via_token(
                 &metadata.to_account_info(),
                 &vault_owner.to_account_info(),
@@ -80,7 +101,12 @@ impl<'info> ClaimResource<'info> {
             )?;
         }
 
-        // Transfer token(ownership)
+        // Transfer token(ownership) or unsold stock
+        let amount = match selling_resource.kind {
+            SellingResourceKind::MasterEdition => 1,
+            SellingResourceKind::Fungible => vault.amount,
+        };
+
         let cpi_program = token_program.to_account_info();
         let cpi_accounts = token::Transfer {
             from: vault.to_account_info(),
@@ -88,7 +114,21 @@ impl<'info> ClaimResource<'info> {
             authority: vault_owner.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, 1)?;
+        token::transfer(cpi_ctx, amount)?;
+
+        selling_resource.state = SellingResourceState::Stopped;
+
+        // Resource left the vault, so `Store` doesn't depend on it anymore
+        store.active_selling_resources = store
+            .active_selling_resources
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(ResourceClaimed {
+            market: market.key(),
+            selling_resource: selling_resource.key(),
+            destination: destination.key(),
+        });
 
         Ok(())
     }
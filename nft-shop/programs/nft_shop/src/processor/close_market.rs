@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, state::MarketState, CloseMarket};
+use crate::{error::ErrorCode, events::MarketClosed, state::MarketState, CloseMarket};
 use anchor_lang::prelude::*;
 
 impl<'info> CloseMarket<'info> {
@@ -16,8 +16,16 @@ impl<'info> CloseMarket<'info> {
             return Err(ErrorCode::MarketIsNotStarted.into());
         }
 
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
         market.state = MarketState::Ended;
 
+        emit!(MarketClosed {
+            market: market.key(),
+        });
+
         Ok(())
     }
 }
@@ -0,0 +1,15 @@
+use crate::{utils::*, ClosePayoutTicket};
+use anchor_lang::prelude::*;
+
+impl<'info> ClosePayoutTicket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &self.market;
+        let clock = &self.clock;
+
+        // Payouts are recorded on `Market` and `Referral`,
+        // so `PayoutTicket` isn't needed to protect from withdrawing twice
+        assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{error::ErrorCode, utils::*, ClosePrimaryMetadataCreators};
+use anchor_lang::prelude::*;
+use mpl_token_metadata::state::TokenMetadataAccount;
+
+impl<'info> ClosePrimaryMetadataCreators<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let metadata = &self.metadata;
+        let metadata_update_authority = &self.metadata_update_authority;
+        let primary_metadata_creators = &self.primary_metadata_creators;
+        let metadata_state: mpl_token_metadata::state::Metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
+
+        assert_keys_equal(
+            metadata_state.update_authority,
+            *metadata_update_authority.key,
+        )?;
+
+        // `withdraw` still needs primary creators once any `Market` of the resource collected funds
+        if !metadata_state.primary_sale_happened && primary_metadata_creators.locked {
+            return Err(ErrorCode::PrimaryMetadataCreatorsAreLocked.into());
+        }
+
+        Ok(())
+    }
+}
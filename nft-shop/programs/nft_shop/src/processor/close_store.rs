@@ -0,0 +1,17 @@
+use crate::{error::ErrorCode, events::StoreClosed, CloseStore};
+use anchor_lang::prelude::*;
+
+impl<'info> CloseStore<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let store = &self.store;
+
+        // Check, that no resource is left in the vaults of this `Store`
+        if store.active_selling_resources != 0 {
+            return Err(ErrorCode::StoreHasActiveSellingResources.into());
+        }
+
+        emit!(StoreClosed { store: store.key() });
+
+        Ok(())
+    }
+}
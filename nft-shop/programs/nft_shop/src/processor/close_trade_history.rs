@@ -0,0 +1,22 @@
+use crate::{error::ErrorCode, state::MarketState, utils::*, CloseTradeHistory};
+use anchor_lang::prelude::*;
+
+impl<'info> CloseTradeHistory<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &self.market;
+        let trade_history = &self.trade_history;
+        let clock = &self.clock;
+
+        // `TradeHistory` keeps buy limits and refunds,
+        // so it can be closed only when `Market` can't use it anymore
+        if market.state == MarketState::Cancelled {
+            if trade_history.funds_paid != 0 {
+                return Err(ErrorCode::FundsAreNotRefunded.into());
+            }
+        } else {
+            assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,19 +1,16 @@
 use crate::{
     error::ErrorCode,
-    state::{MarketState, SellingResourceState, MINIMUM_BALANCE_FOR_SYSTEM_ACCS},
+    events::MarketCreated,
+    state::{Allowlist, Gate, MarketState, PriceTier, PricingCurve, SellingResourceState},
     utils::*,
     CreateMarket,
 };
-use anchor_lang::{
-    prelude::*,
-    solana_program::{program::invoke, system_instruction},
-};
-use anchor_spl::token::accessor;
+use anchor_lang::prelude::*;
 
 impl<'info> CreateMarket<'info> {
     pub fn process(
         &mut self,
-        _treasury_owner_bump: u8,
+        treasury_owner_bump: u8,
         name: String,
         description: String,
         mutable: bool,
@@ -21,6 +18,12 @@ impl<'info> CreateMarket<'info> {
         pieces_in_one_wallet: Option<u64>,
         start_date: u64,
         end_date: Option<u64>,
+        pricing_curve: Option<PricingCurve>,
+        allowlist: Option<Allowlist>,
+        referral_basis_points: u16,
+        allocation: Option<u64>,
+        gate: Option<Gate>,
+        price_tiers: Vec<PriceTier>,
     ) -> Result<()> {
         let market = &mut self.market;
         let store = &self.store;
@@ -46,58 +49,95 @@ impl<'info> CreateMarket<'info> {
             return Err(ErrorCode::PiecesInOneWalletIsTooMuch.into());
         }
 
-        // Only new just created selling resource can be used to create market
-        if selling_resource.state != SellingResourceState::Created {
-            return Err(ErrorCode::SellingResourceAlreadyTaken.into());
-        }
+        if let Some(allocation) = allocation {
+            if allocation == 0 {
+                return Err(ErrorCode::QuantityIsZero.into());
+            }
 
-        // start_date cannot be in the past
-        if start_date < Clock::get().unwrap().unix_timestamp as u64 {
-            return Err(ErrorCode::StartDateIsInPast.into());
-        }
+            // Selling resource can be shared only by `Market`s with allocations
+            if selling_resource.state != SellingResourceState::Created
+                && selling_resource.state != SellingResourceState::InUse
+            {
+                return Err(ErrorCode::SellingResourceAlreadyTaken.into());
+            }
+
+            if selling_resource.markets > 0 && selling_resource.allocated == 0 {
+                return Err(ErrorCode::SellingResourceAlreadyTaken.into());
+            }
+
+            let allocated = selling_resource
+                .allocated
+                .checked_add(allocation)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            if let Some(max_supply) = selling_resource.max_supply {
+                if allocated > max_supply {
+                    return Err(ErrorCode::AllocationIsTooBig.into());
+                }
+            }
 
-        // end_date should not be greater than start_date
-        if end_date.is_some() && start_date > end_date.unwrap() {
-            return Err(ErrorCode::EndDateIsEarlierThanBeginDate.into());
+            if let Some(pieces_in_one_wallet) = pieces_in_one_wallet {
+                if pieces_in_one_wallet > allocation {
+                    return Err(ErrorCode::PiecesInOneWalletIsTooMuch.into());
+                }
+            }
+
+            selling_resource.allocated = allocated;
+        } else if selling_resource.state != SellingResourceState::Created {
+            // Only new just created selling resource can be used to create market
+            return Err(ErrorCode::SellingResourceAlreadyTaken.into());
         }
 
-        let is_native = treasury_mint.key() == System::id();
+        assert_market_dates(
+            start_date,
+            end_date,
+            Clock::get().unwrap().unix_timestamp as u64,
+        )?;
 
-        if !is_native {
-            if treasury_mint.owner != &anchor_spl::token::ID
-                || treasury_holder.owner != &anchor_spl::token::ID
-            {
-                return Err(ProgramError::IllegalOwner.into());
+        // Dutch auction price should go down to a non-zero floor
+        if let Some(pricing_curve) = &pricing_curve {
+            if pricing_curve.floor_price == 0 {
+                return Err(ErrorCode::PriceIsZero.into());
             }
 
-            if accessor::mint(&treasury_holder)? != *treasury_mint.key {
-                return Err(ProgramError::InvalidAccountData.into());
+            if pricing_curve.floor_price > price || pricing_curve.decay_interval == 0 {
+                return Err(ErrorCode::InvalidPricingCurve.into());
             }
+        }
+
+        assert_price_tiers(&price_tiers, &pricing_curve)?;
 
-            if accessor::authority(&treasury_holder)? != treasury_owner.key() {
-                return Err(ProgramError::InvalidAccountData.into());
+        if let Some(allowlist) = &allowlist {
+            // Presale should be in the future and end when the public sale starts
+            if let Some(presale_start_date) = allowlist.presale_start_date {
+                if presale_start_date < Clock::get().unwrap().unix_timestamp as u64 {
+                    return Err(ErrorCode::StartDateIsInPast.into());
+                }
+
+                if presale_start_date >= start_date {
+                    return Err(ErrorCode::PresaleStartDateIsInvalid.into());
+                }
             }
-        } else {
-            // for native SOL we use PDA as a treasury holder
-            // because of security reasons(only program can spend this SOL)
-            if treasury_holder.key != treasury_owner.key {
-                return Err(ProgramError::InvalidAccountData.into());
+
+            if allowlist.presale_price == Some(0) {
+                return Err(ErrorCode::PriceIsZero.into());
             }
+        }
 
-            // we need fund treasury holder account such as it will hold some metadata with SOL balance
-            invoke(
-                &system_instruction::transfer(
-                    &selling_resource_owner.key(),
-                    &treasury_holder.key(),
-                    MINIMUM_BALANCE_FOR_SYSTEM_ACCS,
-                ),
-                &[
-                    selling_resource_owner.to_account_info(),
-                    treasury_holder.to_account_info(),
-                ],
-            )?;
+        // Referrers and platform can't take more than the whole sale
+        if (store.platform_fee_basis_points as u64) + (referral_basis_points as u64)
+            > MAX_BASIS_POINTS as u64
+        {
+            return Err(ErrorCode::ReferralFeeIsTooHigh.into());
         }
 
+        init_treasury(
+            &treasury_mint,
+            &treasury_holder,
+            &treasury_owner.to_account_info(),
+            &selling_resource_owner.to_account_info(),
+        )?;
+
         // Check selling resource ownership
         assert_keys_equal(selling_resource.owner, selling_resource_owner.key())?;
 
@@ -106,6 +146,7 @@ impl<'info> CreateMarket<'info> {
         market.treasury_mint = treasury_mint.key();
         market.treasury_holder = treasury_holder.key();
         market.treasury_owner = treasury_owner.key();
+        market.treasury_owner_bump = treasury_owner_bump;
         market.owner = selling_resource_owner.key();
         market.name = puffed_out_string(name, NAME_MAX_LEN);
         market.description = puffed_out_string(description, DESCRIPTION_MAX_LEN);
@@ -115,7 +156,34 @@ impl<'info> CreateMarket<'info> {
         market.start_date = start_date;
         market.end_date = end_date;
         market.state = MarketState::Created;
+        market.pricing_curve = pricing_curve;
+        market.allowlist = allowlist;
+        market.platform_fee_basis_points = store.platform_fee_basis_points;
+        market.platform_fee_destination = store.platform_fee_destination;
+        market.referral_basis_points = referral_basis_points;
+        market.allocation = allocation;
+        market.gate = gate;
+        market.price_tiers = price_tiers;
         selling_resource.state = SellingResourceState::InUse;
+        selling_resource.markets = selling_resource
+            .markets
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(MarketCreated {
+            store: market.store,
+            market: market.key(),
+            selling_resource: market.selling_resource,
+            owner: market.owner,
+            treasury_mint: market.treasury_mint,
+            price: market.price,
+            start_date: market.start_date,
+            end_date: market.end_date,
+            referral_basis_points: market.referral_basis_points,
+            allocation: market.allocation,
+            gate: market.gate.clone(),
+            price_tiers: market.price_tiers.clone(),
+        });
 
         Ok(())
     }
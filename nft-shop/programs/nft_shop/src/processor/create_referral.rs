@@ -0,0 +1,27 @@
+use crate::{error::ErrorCode, events::ReferralCreated, state::MarketState, CreateReferral};
+use anchor_lang::prelude::*;
+
+impl<'info> CreateReferral<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &self.market;
+        let referral = &mut self.referral;
+        let referrer = &self.referrer;
+
+        // Referrers can join only while `Market` is selling
+        if market.state != MarketState::Created {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        referral.market = market.key();
+        referral.referrer = referrer.key();
+        referral.funds_accrued = 0;
+
+        emit!(ReferralCreated {
+            market: market.key(),
+            referral: referral.key(),
+            referrer: referrer.key(),
+        });
+
+        Ok(())
+    }
+}
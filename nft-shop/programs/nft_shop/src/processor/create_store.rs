@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, utils::*, CreateStore};
+use crate::{error::ErrorCode, events::StoreCreated, utils::*, CreateStore};
 use anchor_lang::prelude::*;
 
 impl<'info> CreateStore<'info> {
@@ -17,6 +17,13 @@ impl<'info> CreateStore<'info> {
         store.admin = store_admin.key();
         store.name = puffed_out_string(name, NAME_MAX_LEN);
         store.description = puffed_out_string(description, DESCRIPTION_MAX_LEN);
+        store.platform_fee_basis_points = 0;
+        store.platform_fee_destination = store_admin.key();
+
+        emit!(StoreCreated {
+            store: store.key(),
+            admin: store.admin,
+        });
 
         Ok(())
     }
@@ -0,0 +1,98 @@
+use crate::{
+    error::ErrorCode,
+    events::SellingResourceInitialized,
+    state::{SellingResourceKind, SellingResourceState},
+    utils::*,
+    InitFungibleSellingResource,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use mpl_token_metadata::state::TokenMetadataAccount;
+
+impl<'info> InitFungibleSellingResource<'info> {
+    pub fn process(&mut self, vault_owner_bump: u8, amount: u64) -> Result<()> {
+        let store = &mut self.store;
+        let store_admin = &self.store_admin;
+        let selling_resource = &mut self.selling_resource;
+        let selling_resource_owner = &self.selling_resource_owner;
+        let resource_mint = &self.resource_mint;
+        let metadata = &self.metadata;
+        let vault = &mut self.vault;
+        let vault_owner = &self.vault_owner;
+        let resource_token = &self.resource_token;
+        let token_program = &self.token_program;
+
+        // Only whole units can be sold
+        if resource_mint.decimals != 0 {
+            return Err(ErrorCode::ResourceMintHasDecimals.into());
+        }
+
+        if amount == 0 {
+            return Err(ErrorCode::QuantityIsZero.into());
+        }
+
+        // Check, that provided metadata is correct
+        assert_derivation(
+            &mpl_token_metadata::id(),
+            metadata,
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                resource_mint.key().as_ref(),
+            ],
+        )?;
+
+        let metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(&metadata.to_account_info())?;
+
+        // Check, that at least one creator exists in primary sale
+        if !metadata.primary_sale_happened {
+            if let Some(creators) = metadata.data.creators {
+                if creators.is_empty() {
+                    return Err(ErrorCode::MetadataCreatorsIsEmpty.into());
+                }
+            } else {
+                return Err(ErrorCode::MetadataCreatorsIsEmpty.into());
+            }
+        }
+
+        // Transfer stock to the vault
+        let cpi_program = token_program.to_account_info();
+        let cpi_accounts = token::Transfer {
+            from: resource_token.to_account_info(),
+            to: vault.to_account_info(),
+            authority: store_admin.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Whole vault balance is on sale
+        vault.reload()?;
+
+        selling_resource.store = store.key();
+        selling_resource.owner = selling_resource_owner.key();
+        selling_resource.resource = resource_mint.key();
+        selling_resource.vault = vault.key();
+        selling_resource.vault_owner = vault_owner.key();
+        selling_resource.vault_owner_bump = vault_owner_bump;
+        selling_resource.supply = 0;
+        selling_resource.max_supply = Some(vault.amount);
+        selling_resource.state = SellingResourceState::Created;
+        selling_resource.kind = SellingResourceKind::Fungible;
+
+        store.active_selling_resources = store
+            .active_selling_resources
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(SellingResourceInitialized {
+            store: store.key(),
+            selling_resource: selling_resource.key(),
+            owner: selling_resource.owner,
+            resource: selling_resource.resource,
+            max_supply: selling_resource.max_supply,
+        });
+
+        Ok(())
+    }
+}
@@ -1,16 +1,17 @@
-use crate::{error::ErrorCode, state::SellingResourceState, utils::*, InitSellingResource};
+use crate::{
+    error::ErrorCode,
+    events::SellingResourceInitialized,
+    state::{SellingResourceKind, SellingResourceState},
+    utils::*,
+    InitSellingResource,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token;
 use mpl_token_metadata::state::TokenMetadataAccount;
 
 impl<'info> InitSellingResource<'info> {
-    pub fn process(
-        &mut self,
-        _master_edition_bump: u8,
-        _vault_owner_bump: u8,
-        max_supply: Option<u64>,
-    ) -> Result<()> {
-        let store = &self.store;
+    pub fn process(&mut self, vault_owner_bump: u8, max_supply: Option<u64>) -> Result<()> {
+        let store = &mut self.store;
         let store_admin = &self.store_admin;
         let selling_resource = &mut self.selling_resource;
         let selling_resource_owner = &self.selling_resource_owner;
@@ -95,9 +96,24 @@ impl<'info> InitSellingResource<'info> {
         selling_resource.resource = resource_mint.key();
         selling_resource.vault = vault.key();
         selling_resource.vault_owner = vault_owner.key();
+        selling_resource.vault_owner_bump = vault_owner_bump;
         selling_resource.supply = 0;
         selling_resource.max_supply = actual_max_supply;
         selling_resource.state = SellingResourceState::Created;
+        selling_resource.kind = SellingResourceKind::MasterEdition;
+
+        store.active_selling_resources = store
+            .active_selling_resources
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(SellingResourceInitialized {
+            store: store.key(),
+            selling_resource: selling_resource.key(),
+            owner: selling_resource.owner,
+            resource: selling_resource.resource,
+            max_supply: selling_resource.max_supply,
+        });
 
         Ok(())
     }
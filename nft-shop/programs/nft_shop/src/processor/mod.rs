@@ -1,9 +1,28 @@
+pub mod accept_admin;
 pub mod buy;
+pub mod buy_fungible;
+pub mod buy_many;
+pub mod cancel_market;
 pub mod change_market;
 pub mod claim_resource;
 pub mod close_market;
+pub mod close_payout_ticket;
+pub mod close_primary_metadata_creators;
+pub mod close_store;
+pub mod close_trade_history;
 pub mod create_market;
+pub mod create_referral;
 pub mod create_store;
+pub mod init_fungible_selling_resource;
 pub mod init_selling_resource;
+pub mod pause_market;
+pub mod refund;
+pub mod resume_market;
 pub mod save_primary_metadata_creators;
+pub mod transfer_admin;
+pub mod update_primary_metadata_creators;
+pub mod update_store;
 pub mod withdraw;
+pub mod withdraw_all;
+pub mod withdraw_platform_fee;
+pub mod withdraw_referral;
@@ -0,0 +1,33 @@
+use crate::{error::ErrorCode, events::MarketPaused, state::MarketState, PauseMarket};
+use anchor_lang::prelude::*;
+
+impl<'info> PauseMarket<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let authority = &self.authority;
+        let clock = &self.clock;
+
+        let now = clock.unix_timestamp as u64;
+
+        // Only selling `Market` can be paused
+        if market.state != MarketState::Created {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        if let Some(end_date) = market.end_date {
+            if now > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        }
+
+        market.state = MarketState::Paused;
+        market.paused_at = Some(now);
+
+        emit!(MarketPaused {
+            market: market.key(),
+            authority: authority.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{error::ErrorCode, events::Refunded, state::MarketState, utils::*, Refund};
+use anchor_lang::prelude::*;
+use spl_token_2022::extension::StateWithExtensions;
+
+impl<'info> Refund<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let trade_history = &mut self.trade_history;
+        let user_wallet = &self.user_wallet;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let treasury_mint = &self.treasury_mint;
+        let treasury_owner = &self.treasury_owner;
+        let destination = &self.destination;
+        let token_program = &self.token_program;
+
+        // Check, that `Market` is `Cancelled`
+        if market.state != MarketState::Cancelled {
+            return Err(ErrorCode::MarketInInvalidState.into());
+        }
+
+        let amount = trade_history.funds_paid;
+        if amount == 0 {
+            return Err(ErrorCode::NothingToRefund.into());
+        }
+
+        // `TradeHistory` is a refund record, so the same funds can't be returned twice
+        trade_history.funds_paid = 0;
+        market.funds_refunded = market
+            .funds_refunded
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let market_key = market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOLDER_PREFIX.as_bytes(),
+            market.treasury_mint.as_ref(),
+            market.selling_resource.as_ref(),
+            market_key.as_ref(),
+            &[market.treasury_owner_bump],
+        ]];
+
+        let is_native = market.treasury_mint == System::id();
+
+        if is_native {
+            if user_wallet.key() != destination.key() {
+                return Err(ErrorCode::InvalidFunderDestination.into());
+            }
+
+            sys_transfer(
+                &treasury_holder.to_account_info(),
+                &destination.to_account_info(),
+                amount,
+                signer_seeds[0],
+            )?;
+        } else {
+            assert_treasury_token_program(
+                &treasury_mint.to_account_info(),
+                &treasury_holder.to_account_info(),
+                &token_program.to_account_info(),
+            )?;
+
+            // Check, that destination belongs to the buyer
+            if destination.owner != treasury_mint.owner {
+                return Err(ProgramError::InvalidArgument.into());
+            }
+
+            let destination_data = destination.data.borrow();
+            let destination_account =
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&destination_data)?;
+            if destination_account.base.owner != user_wallet.key()
+                || destination_account.base.mint != market.treasury_mint
+            {
+                return Err(ErrorCode::InvalidFunderDestination.into());
+            }
+            drop(destination_data);
+
+            transfer_from_treasury(
+                &treasury_holder.to_account_info(),
+                &treasury_mint.to_account_info(),
+                &treasury_owner.to_account_info(),
+                &destination.to_account_info(),
+                &token_program.to_account_info(),
+                amount,
+                signer_seeds[0],
+            )?;
+        }
+
+        emit!(Refunded {
+            market: market.key(),
+            buyer: user_wallet.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{error::ErrorCode, events::MarketResumed, state::MarketState, ResumeMarket};
+use anchor_lang::prelude::*;
+
+impl<'info> ResumeMarket<'info> {
+    pub fn process(&mut self, extend_end_date: bool) -> Result<()> {
+        let market = &mut self.market;
+        let authority = &self.authority;
+        let clock = &self.clock;
+
+        if market.state != MarketState::Paused {
+            return Err(ErrorCode::MarketIsNotPaused.into());
+        }
+
+        let paused_at = market.paused_at.ok_or(ErrorCode::MarketIsNotPaused)?;
+
+        // `Market` which reached `end_date` while paused is ended and may be paid out already
+        if let Some(end_date) = market.end_date {
+            if clock.unix_timestamp as u64 > end_date {
+                return Err(ErrorCode::MarketIsEnded.into());
+            }
+        }
+
+        // Give buyers back the time `Market` was paused
+        if extend_end_date {
+            if let Some(end_date) = market.end_date {
+                let paused_duration = (clock.unix_timestamp as u64)
+                    .checked_sub(paused_at)
+                    .ok_or(ErrorCode::MathOverflow)?;
+
+                market.end_date = Some(
+                    end_date
+                        .checked_add(paused_duration)
+                        .ok_or(ErrorCode::MathOverflow)?,
+                );
+            }
+        }
+
+        market.state = MarketState::Created;
+        market.paused_at = None;
+
+        emit!(MarketResumed {
+            market: market.key(),
+            authority: authority.key(),
+            end_date: market.end_date,
+        });
+
+        Ok(())
+    }
+}
@@ -3,24 +3,14 @@ use anchor_lang::prelude::*;
 use mpl_token_metadata::state::TokenMetadataAccount;
 
 impl<'info> SavePrimaryMetadataCreators<'info> {
-    pub fn process(
-        &mut self,
-        _primary_metadata_creators_bump: u8,
-        creators: Vec<Creator>,
-    ) -> Result<()> {
+    pub fn process(&mut self, creators: Vec<Creator>) -> Result<()> {
         let metadata = &self.metadata;
         let metadata_update_authority = &self.metadata_update_authority;
         let secondary_metadata_creators = &mut self.primary_metadata_creators;
         let metadata_state: mpl_token_metadata::state::Metadata =
             mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
 
-        if creators.len() > MAX_PRIMARY_CREATORS_LEN {
-            return Err(ErrorCode::CreatorsIsGtThanAvailable.into());
-        }
-
-        if creators.is_empty() {
-            return Err(ErrorCode::CreatorsIsEmpty.into());
-        }
+        assert_primary_metadata_creators(&creators)?;
 
         if !metadata_state.is_mutable {
             return Err(ErrorCode::MetadataShouldBeMutable.into());
@@ -0,0 +1,14 @@
+use crate::TransferAdmin;
+use anchor_lang::prelude::*;
+
+impl<'info> TransferAdmin<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let store = &mut self.store;
+        let new_admin = &self.new_admin;
+
+        // Admin is changed only after `new_admin` accepts it
+        store.pending_admin = Some(new_admin.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{error::ErrorCode, state::Creator, utils::*, UpdatePrimaryMetadataCreators};
+use anchor_lang::prelude::*;
+use mpl_token_metadata::state::TokenMetadataAccount;
+
+impl<'info> UpdatePrimaryMetadataCreators<'info> {
+    pub fn process(&mut self, creators: Vec<Creator>) -> Result<()> {
+        let metadata = &self.metadata;
+        let metadata_update_authority = &self.metadata_update_authority;
+        let primary_metadata_creators = &mut self.primary_metadata_creators;
+        let metadata_state: mpl_token_metadata::state::Metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
+
+        assert_primary_metadata_creators(&creators)?;
+
+        if metadata_state.primary_sale_happened {
+            return Err(ErrorCode::PrimarySaleIsNotAllowed.into());
+        }
+
+        assert_keys_equal(
+            metadata_state.update_authority,
+            *metadata_update_authority.key,
+        )?;
+
+        // Creators are locked as soon as any `Market` of the resource collected funds
+        if primary_metadata_creators.locked {
+            return Err(ErrorCode::PrimaryMetadataCreatorsAreLocked.into());
+        }
+
+        primary_metadata_creators.creators = creators;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{error::ErrorCode, events::StoreUpdated, utils::*, UpdateStore};
+use anchor_lang::prelude::*;
+
+impl<'info> UpdateStore<'info> {
+    pub fn process(
+        &mut self,
+        new_name: Option<String>,
+        new_description: Option<String>,
+        new_platform_fee_basis_points: Option<u16>,
+        new_platform_fee_destination: Option<Pubkey>,
+    ) -> Result<()> {
+        let store = &mut self.store;
+
+        if let Some(new_name) = new_name {
+            if new_name.len() > NAME_MAX_LEN {
+                return Err(ErrorCode::NameIsTooLong.into());
+            }
+
+            store.name = puffed_out_string(new_name, NAME_MAX_LEN);
+        }
+
+        if let Some(new_description) = new_description {
+            if new_description.len() > DESCRIPTION_MAX_LEN {
+                return Err(ErrorCode::DescriptionIsTooLong.into());
+            }
+
+            store.description = puffed_out_string(new_description, DESCRIPTION_MAX_LEN);
+        }
+
+        // New fee is applied only to markets created after this change
+        if let Some(new_platform_fee_basis_points) = new_platform_fee_basis_points {
+            if new_platform_fee_basis_points > MAX_BASIS_POINTS {
+                return Err(ErrorCode::PlatformFeeIsTooHigh.into());
+            }
+
+            store.platform_fee_basis_points = new_platform_fee_basis_points;
+        }
+
+        if let Some(new_platform_fee_destination) = new_platform_fee_destination {
+            store.platform_fee_destination = new_platform_fee_destination;
+        }
+
+        emit!(StoreUpdated {
+            store: store.key(),
+            platform_fee_basis_points: store.platform_fee_basis_points,
+            platform_fee_destination: store.platform_fee_destination,
+        });
+
+        Ok(())
+    }
+}
@@ -1,24 +1,17 @@
 use crate::{
     error::ErrorCode,
-    state::{Creator, MarketState, PrimaryMetadataCreators},
+    events::{PayoutRole, PayoutWithdrawn},
+    state::MarketState,
     utils::*,
     Withdraw,
 };
-use anchor_lang::{prelude::*, solana_program::borsh::try_from_slice_unchecked};
-use anchor_spl::{
-    associated_token::{self, get_associated_token_address},
-    token,
-};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token;
 use mpl_token_metadata::state::TokenMetadataAccount;
 
 impl<'info> Withdraw<'info> {
-    pub fn process(
-        &mut self,
-        treasury_owner_bump: u8,
-        _payout_ticket_bump: u8,
-        remaining_accounts: &[AccountInfo],
-    ) -> Result<()> {
-        let market = &self.market;
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo]) -> Result<()> {
+        let market = &mut self.market;
         let token_program = &self.token_program;
         let associated_token_program = &self.associated_token_program;
         let system_program = &self.system_program;
@@ -34,6 +27,7 @@ impl<'info> Withdraw<'info> {
         let clock = &self.clock;
         let metadata = &self.metadata.to_account_info();
 
+        let market_key = market.key();
         let selling_resource_key = selling_resource.key();
         let treasury_mint_key = market.treasury_mint;
         let funder_key = funder.key();
@@ -44,14 +38,12 @@ impl<'info> Withdraw<'info> {
         }
 
         // Check, that `Market` is `Ended`
-        if let Some(end_date) = market.end_date {
-            if clock.unix_timestamp as u64 <= end_date {
-                return Err(ErrorCode::MarketInInvalidState.into());
-            }
-        } else if market.state != MarketState::Ended {
-            return Err(ErrorCode::MarketInInvalidState.into());
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
         }
 
+        assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+
         // Check, that provided metadata is correct
         assert_derivation(
             &mpl_token_metadata::id(),
@@ -64,54 +56,56 @@ impl<'info> Withdraw<'info> {
         )?;
 
         // Obtain right creators according to sale type
+        let metadata_key = metadata.key();
         let metadata: mpl_token_metadata::state::Metadata =
             mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
-        let actual_creators = if !metadata.primary_sale_happened {
-            if remaining_accounts.is_empty() {
-                return Err(ErrorCode::PrimaryMetadataCreatorsNotProvided.into());
-            }
-
-            let primary_metadata_creators_data = remaining_accounts[0].data.borrow()[8..].to_vec();
-            let primary_metadata_creators = try_from_slice_unchecked::<PrimaryMetadataCreators>(
-                &primary_metadata_creators_data,
-            )?;
-            Box::new(Some(primary_metadata_creators.creators))
-        } else if let Some(creators) = metadata.data.creators {
-            Box::new(Some(
-                creators
-                    .iter()
-                    .map(|item| Creator::from(item.clone()))
-                    .collect(),
-            ))
-        } else {
-            Box::new(None)
-        };
+        let actual_creators =
+            payout_creators(&metadata_key, &metadata, remaining_accounts.first())?;
 
         // Check, that funder is `Creator` or `Market` owner
         // `Some` mean funder is `Creator`
         // `None` mean funder is `Market` owner
-        let funder_creator = if let Some(creators) = *actual_creators {
-            let funder_creator = creators.iter().find(|c| c.address == funder_key).cloned();
-            if funder_creator.is_none() && funder_key != market.owner {
-                return Err(ErrorCode::FunderIsInvalid.into());
-            }
-
-            funder_creator
-        } else if funder_key != market.owner {
+        let funder_creator = actual_creators
+            .iter()
+            .find(|c| c.address == funder_key)
+            .cloned();
+        if funder_creator.is_none() && funder_key != market.owner {
             return Err(ErrorCode::FunderIsInvalid.into());
-        } else {
-            None
-        };
+        }
 
         // Check, that user can withdraw funds(first time)
-        if payout_ticket.used {
+        // `Market` keeps the record, because `PayoutTicket` can be closed
+        if payout_ticket.used || market.paid_out.contains(&funder_key) {
             return Err(ErrorCode::PayoutTicketExists.into());
         } else {
             payout_ticket.used = true;
+            payout_ticket.market = market.key();
+            payout_ticket.payer = selling_resource_owner.key();
+            market.paid_out.push(funder_key);
         }
 
         let is_native = market.treasury_mint == System::id();
 
+        // Creators and `Market` owner share what is left after the platform fee and referrals
+        let funds_collected = market
+            .funds_collected
+            .checked_sub(calculate_platform_fee(
+                market.funds_collected,
+                market.platform_fee_basis_points as u64,
+            )?)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_sub(market.referral_funds)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Creator who owns `Market` receives both shares only after primary sale
+        let role = match &funder_creator {
+            Some(_) if funder_key == market.owner && metadata.primary_sale_happened => {
+                PayoutRole::CreatorAndMarketOwner
+            }
+            Some(_) => PayoutRole::Creator,
+            None => PayoutRole::MarketOwner,
+        };
+
         let amount = if metadata.primary_sale_happened {
             if funder_creator.is_some() && funder_key == market.owner {
                 // if funder is NFT creator and market owner at the same time
@@ -119,13 +113,13 @@ impl<'info> Withdraw<'info> {
                 let funder_creator = funder_creator.as_ref().unwrap();
 
                 let funder_as_creator_share = calculate_secondary_shares_for_creator(
-                    market.funds_collected,
+                    funds_collected,
                     metadata.data.seller_fee_basis_points as u64,
                     funder_creator.share as u64,
                 )?;
 
                 let funder_as_market_owner_share = calculate_secondary_shares_for_market_owner(
-                    market.funds_collected,
+                    funds_collected,
                     metadata.data.seller_fee_basis_points as u64,
                 )?;
 
@@ -134,21 +128,18 @@ impl<'info> Withdraw<'info> {
                     .ok_or(ErrorCode::MathOverflow)?
             } else if let Some(funder_creator) = &funder_creator {
                 calculate_secondary_shares_for_creator(
-                    market.funds_collected,
+                    funds_collected,
                     metadata.data.seller_fee_basis_points as u64,
                     funder_creator.share as u64,
                 )?
             } else {
                 calculate_secondary_shares_for_market_owner(
-                    market.funds_collected,
+                    funds_collected,
                     metadata.data.seller_fee_basis_points as u64,
                 )?
             }
         } else if let Some(funder_creator) = funder_creator {
-            calculate_primary_shares_for_creator(
-                market.funds_collected,
-                funder_creator.share as u64,
-            )?
+            calculate_primary_shares_for_creator(funds_collected, funder_creator.share as u64)?
         } else {
             return Err(ErrorCode::MarketOwnerDoesntHaveShares.into());
         };
@@ -158,7 +149,8 @@ impl<'info> Withdraw<'info> {
             HOLDER_PREFIX.as_bytes(),
             treasury_mint_key.as_ref(),
             selling_resource_key.as_ref(),
-            &[treasury_owner_bump],
+            market_key.as_ref(),
+            &[market.treasury_owner_bump],
         ]];
 
         if is_native {
@@ -173,16 +165,14 @@ impl<'info> Withdraw<'info> {
                 signer_seeds[0],
             )?;
         } else {
-            if *treasury_mint.owner != spl_token::id() {
-                return Err(ProgramError::InvalidArgument.into());
-            }
-
-            if *treasury_holder.owner != spl_token::id() {
-                return Err(ProgramError::InvalidArgument.into());
-            }
+            assert_treasury_token_program(
+                &treasury_mint.to_account_info(),
+                &treasury_holder.to_account_info(),
+                &token_program.to_account_info(),
+            )?;
 
             let associated_token_account =
-                get_associated_token_address(&funder_key, &market.treasury_mint);
+                associated_token_address(&funder_key, &market.treasury_mint, token_program.key);
 
             // Check, that provided destination is associated token account
             if associated_token_account != destination.key() {
@@ -204,16 +194,25 @@ impl<'info> Withdraw<'info> {
                 associated_token::create(cpi_ctx)?;
             }
 
-            let cpi_program = token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: treasury_holder.to_account_info(),
-                to: destination.to_account_info(),
-                authority: treasury_owner.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::transfer(cpi_ctx, amount)?;
+            transfer_from_treasury(
+                &treasury_holder.to_account_info(),
+                &treasury_mint.to_account_info(),
+                &treasury_owner.to_account_info(),
+                &destination.to_account_info(),
+                &token_program.to_account_info(),
+                amount,
+                signer_seeds[0],
+            )?;
         }
 
+        emit!(PayoutWithdrawn {
+            market: market.key(),
+            funder: funder_key,
+            destination: destination.key(),
+            amount,
+            role,
+        });
+
         Ok(())
     }
 }
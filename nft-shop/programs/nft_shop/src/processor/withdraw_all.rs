@@ -0,0 +1,206 @@
+use crate::{
+    error::ErrorCode,
+    events::PayoutWithdrawn,
+    state::{MarketState, PayoutTicket},
+    utils::*,
+    WithdrawAll,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token;
+use mpl_token_metadata::state::TokenMetadataAccount;
+
+impl<'info> WithdrawAll<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let market = &mut self.market;
+        let token_program = &self.token_program;
+        let associated_token_program = &self.associated_token_program;
+        let system_program = &self.system_program;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let treasury_mint = Box::new(&self.treasury_mint);
+        let treasury_owner = &self.treasury_owner;
+        let selling_resource = &self.selling_resource;
+        let selling_resource_owner = &self.selling_resource_owner;
+        let clock = &self.clock;
+        let metadata = &self.metadata.to_account_info();
+
+        let market_key = market.key();
+        let selling_resource_key = selling_resource.key();
+        let treasury_mint_key = market.treasury_mint;
+
+        // Check, that provided right Market owner
+        if !selling_resource_owner.key.eq(&market.owner) {
+            return Err(ErrorCode::SellingResourceOwnerInvalid.into());
+        }
+
+        // Check, that `Market` is `Ended`
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+
+        // Check, that provided metadata is correct
+        assert_derivation(
+            &mpl_token_metadata::id(),
+            metadata,
+            &[
+                mpl_token_metadata::state::PREFIX.as_bytes(),
+                mpl_token_metadata::id().as_ref(),
+                selling_resource.resource.as_ref(),
+            ],
+        )?;
+
+        // Obtain right creators according to sale type
+        let metadata_key = metadata.key();
+        let metadata: mpl_token_metadata::state::Metadata =
+            mpl_token_metadata::state::Metadata::from_account_info(metadata)?;
+        let actual_creators =
+            payout_creators(&metadata_key, &metadata, remaining_accounts.first())?;
+        let payout_accounts = if !metadata.primary_sale_happened {
+            &remaining_accounts[1..]
+        } else {
+            remaining_accounts
+        };
+
+        // Every creator in metadata order, followed by `Market` owner after primary sale
+        // if he isn't one of the creators
+        let payees = market_payees(market, &metadata, &actual_creators)?;
+
+        // Check, that `funder`, `destination` and `payout_ticket` are passed for every payee
+        if payout_accounts.len() != payees.len() * PAYOUT_ACCOUNTS_LEN {
+            return Err(ErrorCode::PayoutAccountsMismatch.into());
+        }
+
+        let is_native = market.treasury_mint == System::id();
+
+        if !is_native {
+            assert_treasury_token_program(
+                &treasury_mint.to_account_info(),
+                &treasury_holder.to_account_info(),
+                &token_program.to_account_info(),
+            )?;
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOLDER_PREFIX.as_bytes(),
+            treasury_mint_key.as_ref(),
+            selling_resource_key.as_ref(),
+            market_key.as_ref(),
+            &[market.treasury_owner_bump],
+        ]];
+
+        for ((funder_key, amount, role), accounts) in payees
+            .into_iter()
+            .zip(payout_accounts.chunks(PAYOUT_ACCOUNTS_LEN))
+        {
+            let funder = &accounts[0];
+            let destination = &accounts[1];
+            let payout_ticket = &accounts[2];
+
+            if funder_key != funder.key() {
+                return Err(ErrorCode::PayoutAccountsMismatch.into());
+            }
+
+            let payout_ticket_bump = assert_derivation(
+                &crate::id(),
+                payout_ticket,
+                &[
+                    PAYOUT_TICKET_PREFIX.as_bytes(),
+                    market_key.as_ref(),
+                    funder_key.as_ref(),
+                ],
+            )?;
+
+            // Payees who have already withdrawn through `withdraw` are skipped
+            if market.paid_out.contains(&funder_key) {
+                continue;
+            }
+
+            if payout_ticket.data_is_empty() {
+                sys_create_pda(
+                    &selling_resource_owner.to_account_info(),
+                    payout_ticket,
+                    &system_program.to_account_info(),
+                    PayoutTicket::LEN,
+                    &[
+                        PAYOUT_TICKET_PREFIX.as_bytes(),
+                        market_key.as_ref(),
+                        funder_key.as_ref(),
+                        &[payout_ticket_bump],
+                    ],
+                )?;
+            } else if Account::<PayoutTicket>::try_from(payout_ticket)?.used {
+                continue;
+            }
+
+            let ticket = PayoutTicket {
+                used: true,
+                market: market_key,
+                payer: selling_resource_owner.key(),
+            };
+            {
+                let mut data = payout_ticket.try_borrow_mut_data()?;
+                let mut writer: &mut [u8] = &mut data;
+                ticket.try_serialize(&mut writer)?;
+            }
+            market.paid_out.push(funder_key);
+
+            // Transfer royalties
+            if is_native {
+                if funder_key != destination.key() {
+                    return Err(ErrorCode::InvalidFunderDestination.into());
+                }
+
+                sys_transfer(
+                    &treasury_holder.to_account_info(),
+                    destination,
+                    amount,
+                    signer_seeds[0],
+                )?;
+            } else {
+                let associated_token_account =
+                    associated_token_address(&funder_key, &market.treasury_mint, token_program.key);
+
+                // Check, that provided destination is associated token account
+                if associated_token_account != destination.key() {
+                    return Err(ErrorCode::InvalidFunderDestination.into());
+                }
+
+                // Check, that provided destination is exists
+                if destination.lamports() == 0 && destination.data_is_empty() {
+                    let cpi_program = associated_token_program.to_account_info();
+                    let cpi_accounts = associated_token::Create {
+                        payer: selling_resource_owner.to_account_info(),
+                        associated_token: destination.clone(),
+                        authority: funder.clone(),
+                        mint: treasury_mint.to_account_info(),
+                        token_program: token_program.to_account_info(),
+                        system_program: system_program.to_account_info(),
+                    };
+                    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                    associated_token::create(cpi_ctx)?;
+                }
+
+                transfer_from_treasury(
+                    &treasury_holder.to_account_info(),
+                    &treasury_mint.to_account_info(),
+                    &treasury_owner.to_account_info(),
+                    destination,
+                    &token_program.to_account_info(),
+                    amount,
+                    signer_seeds[0],
+                )?;
+            }
+
+            emit!(PayoutWithdrawn {
+                market: market_key,
+                funder: funder_key,
+                destination: destination.key(),
+                amount,
+                role,
+            });
+        }
+
+        Ok(())
+    }
+}
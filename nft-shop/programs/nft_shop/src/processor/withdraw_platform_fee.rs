@@ -0,0 +1,124 @@
+use crate::{
+    error::ErrorCode,
+    events::{PayoutRole, PayoutWithdrawn},
+    state::MarketState,
+    utils::*,
+    WithdrawPlatformFee,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token;
+
+impl<'info> WithdrawPlatformFee<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let token_program = &self.token_program;
+        let associated_token_program = &self.associated_token_program;
+        let system_program = &self.system_program;
+        let treasury_holder = Box::new(&self.treasury_holder);
+        let treasury_mint = Box::new(&self.treasury_mint);
+        let treasury_owner = &self.treasury_owner;
+        let destination = &self.destination;
+        let platform_fee_destination = &self.platform_fee_destination;
+        let store_admin = &self.store_admin;
+        let payout_ticket = &mut self.payout_ticket;
+        let clock = &self.clock;
+
+        // Check, that `Market` is `Ended`
+        if market.state == MarketState::Cancelled {
+            return Err(ErrorCode::MarketIsCancelled.into());
+        }
+
+        assert_market_is_ended(market, clock.unix_timestamp as u64)?;
+
+        // Check, that platform fee can be withdrawn only once
+        if payout_ticket.used || market.platform_fee_withdrawn {
+            return Err(ErrorCode::PayoutTicketExists.into());
+        } else {
+            payout_ticket.used = true;
+            payout_ticket.market = market.key();
+            payout_ticket.payer = store_admin.key();
+            market.platform_fee_withdrawn = true;
+        }
+
+        let is_native = market.treasury_mint == System::id();
+
+        let amount = calculate_platform_fee(
+            market.funds_collected,
+            market.platform_fee_basis_points as u64,
+        )?;
+
+        let market_key = market.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOLDER_PREFIX.as_bytes(),
+            market.treasury_mint.as_ref(),
+            market.selling_resource.as_ref(),
+            market_key.as_ref(),
+            &[market.treasury_owner_bump],
+        ]];
+
+        if is_native {
+            if platform_fee_destination.key() != destination.key() {
+                return Err(ErrorCode::InvalidFunderDestination.into());
+            }
+
+            sys_transfer(
+                &treasury_holder.to_account_info(),
+                &destination.to_account_info(),
+                amount,
+                signer_seeds[0],
+            )?;
+        } else {
+            assert_treasury_token_program(
+                &treasury_mint.to_account_info(),
+                &treasury_holder.to_account_info(),
+                &token_program.to_account_info(),
+            )?;
+
+            let associated_token_account = associated_token_address(
+                &platform_fee_destination.key(),
+                &market.treasury_mint,
+                token_program.key,
+            );
+
+            // Check, that provided destination is associated token account
+            if associated_token_account != destination.key() {
+                return Err(ErrorCode::InvalidFunderDestination.into());
+            }
+
+            // Check, that provided destination is exists
+            if destination.lamports() == 0 && destination.data_is_empty() {
+                let cpi_program = associated_token_program.to_account_info();
+                let cpi_accounts = associated_token::Create {
+                    payer: store_admin.to_account_info(),
+                    associated_token: destination.to_account_info(),
+                    authority: platform_fee_destination.to_account_info(),
+                    mint: treasury_mint.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                associated_token::create(cpi_ctx)?;
+            }
+
+            transfer_from_treasury(
+                &treasury_holder.to_account_info(),
+                &treasury_mint.to_account_info(),
+                &treasury_owner.to_account_info(),
+                &destination.to_account_info(),
+                &token_program.to_account_info(),
+                amount,
+                signer_seeds[0],
+            )?;
+        }
+
+        emit!(PayoutWithdrawn {
+            market: market.key(),
+            funder: platform_fee_destination.key(),
+            destination: destination.key(),
+            amount,
+            role: PayoutRole::Platform,
+        });
+
+        Ok(())
+    }
+}
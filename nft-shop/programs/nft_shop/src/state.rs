@@ -1,6 +1,9 @@
 //! Module provide program defined state
 
-use crate::utils::{DESCRIPTION_DEFAULT_SIZE, MAX_PRIMARY_CREATORS_LEN, NAME_DEFAULT_SIZE};
+use crate::utils::{
+    DESCRIPTION_DEFAULT_SIZE, MAX_PAYEES_LEN, MAX_PRICE_TIERS_LEN, MAX_PRIMARY_CREATORS_LEN,
+    NAME_DEFAULT_SIZE,
+};
 use anchor_lang::prelude::*;
 use mpl_token_metadata::state::Creator as MPL_Creator;
 use std::convert::From;
@@ -13,10 +16,17 @@ pub struct Store {
     pub admin: Pubkey,
     pub name: String,
     pub description: String,
+    // Proposed by `transfer_admin`, becomes `admin` after `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    // `SellingResource`s which still keep the resource in their vault
+    pub active_selling_resources: u64,
+    // Cut of every primary sale, taken before creators and `Market` owner shares
+    pub platform_fee_basis_points: u16,
+    pub platform_fee_destination: Pubkey,
 }
 
 impl Store {
-    pub const LEN: usize = 8 + 32 + NAME_DEFAULT_SIZE + DESCRIPTION_DEFAULT_SIZE;
+    pub const LEN: usize = 8 + 32 + NAME_DEFAULT_SIZE + DESCRIPTION_DEFAULT_SIZE + 33 + 8 + 2 + 32;
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
@@ -28,6 +38,14 @@ pub enum SellingResourceState {
     Stopped,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub enum SellingResourceKind {
+    // Every buy prints a new edition of the master edition kept in the vault
+    MasterEdition,
+    // Every buy transfers units of a zero decimals mint from the stock kept in the vault
+    Fungible,
+}
+
 #[account]
 pub struct SellingResource {
     pub store: Pubkey,
@@ -38,16 +56,97 @@ pub struct SellingResource {
     pub supply: u64,
     pub max_supply: Option<u64>,
     pub state: SellingResourceState,
+    pub kind: SellingResourceKind,
+    // Sum of `Market::allocation` of all `Market`s created from this resource
+    pub allocated: u64,
+    // `Market`s which didn't release the resource with `claim_resource` yet
+    pub markets: u64,
+    // Canonical bump of `vault_owner`, found at creation
+    pub vault_owner_bump: u8,
 }
 
 impl SellingResource {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 9 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 9 + 1 + 1 + 8 + 8 + 1;
+}
+
+/// Declining price schedule of a Dutch auction `Market`.
+/// The price starts at `Market::price` and goes down by `decay_step`
+/// every `decay_interval` seconds after `Market::start_date` until it reaches `floor_price`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PricingCurve {
+    pub floor_price: u64,
+    pub decay_interval: u64,
+    pub decay_step: u64,
+}
+
+impl PricingCurve {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// Fixed price of pieces sold while `SellingResource::supply` is under `supply_threshold`.
+/// Pieces past the last tier are sold at `Market::price`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PriceTier {
+    pub supply_threshold: u64,
+    pub price: u64,
+}
+
+impl PriceTier {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Merkle allowlist of a `Market`, leaves are built by `utils::allowlist_leaf`.
+/// Presale lasts from `presale_start_date` until `Market::start_date`, only allowlisted wallets can buy there.
+/// Without presale every buy requires an allowlist proof.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Allowlist {
+    pub merkle_root: [u8; 32],
+    pub presale_start_date: Option<u64>,
+    // `None` mean presale at `Market::price`
+    pub presale_price: Option<u64>,
+}
+
+impl Allowlist {
+    pub const LEN: usize = 32 + 9 + 9;
+}
+
+// Proof of the buyer's wallet and allocation in `Allowlist::merkle_root`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum GateKind {
+    // Buyer must hold a verified member of the Metaplex collection `Gate::key`
+    Collection,
+    // Buyer must hold a token of mint `Gate::key`
+    Mint,
+}
+
+/// Token a buyer has to hold to buy from a gated `Market`.
+/// With `limit` every gate token can buy at most `limit` pieces, whichever wallet holds it,
+/// usage is counted in `GateUsage` of the gate token mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub key: Pubkey,
+    pub limit: Option<u64>,
+}
+
+impl Gate {
+    pub const LEN: usize = 1 + 32 + 9;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub enum MarketState {
     Created,
     Ended,
+    // Buyers can take their funds back with `refund`
+    Cancelled,
+    // Sales are stopped until `resume_market`
+    Paused,
 }
 
 #[account]
@@ -60,6 +159,7 @@ pub struct Market {
     pub owner: Pubkey,
     pub name: String,
     pub description: String,
+    // `false` freezes every field of `Market`, including dates and treasury
     pub mutable: bool,
     pub price: u64,
     pub pieces_in_one_wallet: Option<u64>,
@@ -68,6 +168,41 @@ pub struct Market {
     pub state: MarketState,
     // Need this field to calculate royalties at withdraw
     pub funds_collected: u64,
+    // `None` mean flat `price`
+    pub pricing_curve: Option<PricingCurve>,
+    // `None` mean any wallet can buy
+    pub allowlist: Option<Allowlist>,
+    // Copied from `Store` at creation, so the admin can't change fee of a running `Market`
+    pub platform_fee_basis_points: u16,
+    pub platform_fee_destination: Pubkey,
+    // Share of every sale made through a `Referral`
+    pub referral_basis_points: u16,
+    // Total accrued by all referrers, it's deducted before creators and owner shares
+    pub referral_funds: u64,
+    // Set while `Market` is `Paused`, used to push `end_date` forward on resume
+    pub paused_at: Option<u64>,
+    // Part of `SellingResource::max_supply` this `Market` can sell, `None` mean the whole resource
+    pub allocation: Option<u64>,
+    // Pieces sold by this `Market`
+    pub sold: u64,
+    // Set by `claim_resource`
+    pub claimed: bool,
+    // Canonical bump of `treasury_owner`, found at creation
+    pub treasury_owner_bump: u8,
+    // `None` mean buyers don't have to hold a gate token
+    pub gate: Option<Gate>,
+    // Ordered by `supply_threshold`, empty mean flat `price` or `pricing_curve`
+    pub price_tiers: Vec<PriceTier>,
+    // Creators and `Market` owner who already withdrew their share, kept after `PayoutTicket` is closed
+    pub paid_out: Vec<Pubkey>,
+    // Set by `withdraw_platform_fee`
+    pub platform_fee_withdrawn: bool,
+    // Part of `referral_funds` paid by `withdraw_referral`
+    pub referral_funds_withdrawn: u64,
+    // Set by `cancel_market`, buyers have `REFUND_PERIOD` to get refunds before resource can be claimed
+    pub cancelled_at: Option<u64>,
+    // Part of `funds_collected` returned by `refund`
+    pub funds_refunded: u64,
 }
 
 impl Market {
@@ -87,7 +222,30 @@ impl Market {
         + 9
         + 1
         + 8
-        + 1;
+        + 1
+        + 1
+        + PricingCurve::LEN
+        + 1
+        + Allowlist::LEN
+        + 2
+        + 32
+        + 2
+        + 8
+        + 9
+        + 9
+        + 8
+        + 1
+        + 1
+        + 1
+        + Gate::LEN
+        + 4
+        + PriceTier::LEN * MAX_PRICE_TIERS_LEN
+        + 4
+        + 32 * MAX_PAYEES_LEN
+        + 1
+        + 8
+        + 9
+        + 8;
 }
 
 #[account]
@@ -96,29 +254,65 @@ pub struct TradeHistory {
     pub market: Pubkey,
     pub wallet: Pubkey,
     pub already_bought: u64,
+    // What `wallet` paid in total, returned by `refund` if `Market` is `Cancelled`
+    pub funds_paid: u64,
 }
 
 impl TradeHistory {
-    pub const LEN: usize = 8 + 32 + 32 + 8;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
 }
 
 #[account]
 pub struct PrimaryMetadataCreators {
+    // Empty only if it was created by a sale, then metadata creators are paid
     pub creators: Vec<Creator>,
+    // Set on the first sale of the resource, `withdraw` pays creators from this list
+    pub locked: bool,
 }
 
 impl PrimaryMetadataCreators {
-    pub const LEN: usize = 8 + ((32 + 1 + 1) * MAX_PRIMARY_CREATORS_LEN + 1);
+    pub const LEN: usize = 8 + 4 + (32 + 1 + 1) * MAX_PRIMARY_CREATORS_LEN + 1;
 }
 
 #[account]
 #[derive(Default)]
 pub struct PayoutTicket {
     pub used: bool,
+    pub market: Pubkey,
+    // Receives rent back in `close_payout_ticket`
+    pub payer: Pubkey,
 }
 
 impl PayoutTicket {
-    pub const LEN: usize = 9;
+    pub const LEN: usize = 9 + 32 + 32;
+}
+
+#[account]
+#[derive(Default)]
+pub struct Referral {
+    pub market: Pubkey,
+    pub referrer: Pubkey,
+    // Referrer's share of sales, paid out by `withdraw_referral`
+    pub funds_accrued: u64,
+    // Set by `withdraw_referral`
+    pub withdrawn: bool,
+}
+
+impl Referral {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct GateUsage {
+    pub market: Pubkey,
+    pub gate_mint: Pubkey,
+    // Pieces bought with `gate_mint` token, limited by `Gate::limit`
+    pub bought: u64,
+}
+
+impl GateUsage {
+    pub const LEN: usize = 8 + 32 + 32 + 8;
 }
 
 // Unfortunate duplication of token metadata so that IDL picks it up
@@ -1,11 +1,26 @@
 //! Module provide runtime utilities
 
-use crate::ErrorCode;
+use crate::{
+    events::PayoutRole,
+    state::{
+        AllowlistProof, Creator, GateKind, GateUsage, Market, MarketState, PriceTier, PricingCurve,
+        PrimaryMetadataCreators, Referral, SellingResource, SellingResourceState, TradeHistory,
+        MINIMUM_BALANCE_FOR_SYSTEM_ACCS,
+    },
+    ErrorCode,
+};
 use anchor_lang::{
     prelude::*,
-    solana_program::{program::invoke_signed, system_instruction},
+    solana_program::{
+        keccak,
+        program::{invoke, invoke_signed},
+        system_instruction,
+    },
 };
-use mpl_token_metadata::instruction as mpl_instruction;
+use anchor_spl::token;
+use mpl_token_metadata::{instruction as mpl_instruction, state::TokenMetadataAccount};
+use spl_token_2022::extension::StateWithExtensions;
+use std::cmp::Ordering;
 
 pub const NAME_MAX_LEN: usize = 40; // max len of a string buffer in bytes
 pub const NAME_DEFAULT_SIZE: usize = 4 + NAME_MAX_LEN; // max lenght of serialized string (str_len + <buffer>)
@@ -15,9 +30,19 @@ pub const HOLDER_PREFIX: &str = "holder";
 pub const HISTORY_PREFIX: &str = "history";
 pub const VAULT_OWNER_PREFIX: &str = "mt_vault";
 pub const PAYOUT_TICKET_PREFIX: &str = "payout_ticket";
+pub const PLATFORM_FEE_TICKET_PREFIX: &str = "platform_fee_ticket";
+pub const REFERRAL_PREFIX: &str = "referral";
+pub const REFERRAL_TICKET_PREFIX: &str = "referral_ticket";
 pub const PRIMARY_METADATA_CREATORS_PREFIX: &str = "primary_creators";
+pub const GATE_USAGE_PREFIX: &str = "gate_usage";
 pub const FLAG_ACCOUNT_SIZE: usize = 1; // Size for flag account to indicate something
 pub const MAX_PRIMARY_CREATORS_LEN: usize = 5; // Total allowed creators in `PrimaryMetadataCreators`
+pub const MAX_BASIS_POINTS: u16 = 10000;
+pub const EDITION_ACCOUNTS_LEN: usize = 5; // Accounts passed per edition in `buy_many`
+pub const PAYOUT_ACCOUNTS_LEN: usize = 3; // Accounts passed per payee in `withdraw_all`
+pub const MAX_PRICE_TIERS_LEN: usize = 5; // Total allowed tiers in `Market::price_tiers`
+pub const MAX_PAYEES_LEN: usize = 6; // Creators and `Market` owner recorded in `Market::paid_out`
+pub const REFUND_PERIOD: u64 = 30 * 24 * 60 * 60; // Seconds unrefunded buyers keep resource of `Cancelled` `Market`
 
 /// Runtime derivation check
 pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<u8> {
@@ -45,6 +70,98 @@ pub fn sys_transfer<'a>(
     Ok(())
 }
 
+/// Wrapper of `transfer_checked` instruction from SPL Token or Token-2022 program
+#[inline(always)]
+pub fn spl_transfer_checked<'a>(
+    token_program: &AccountInfo<'a>,
+    from: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint.data.borrow())?
+        .base
+        .decimals;
+
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            from.key,
+            mint.key,
+            to.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            from.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Wrapper of `create_account` instruction from `system_program` program for a PDA owned by this program.
+/// PDA which already holds lamports is topped up to rent exemption, allocated and assigned instead
+#[inline(always)]
+pub fn sys_create_pda<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(space);
+
+    if new_account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                &crate::id(),
+            ),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+
+        return Ok(());
+    }
+
+    // `create_account` fails for an address which anyone has sent lamports to
+    let shortfall = lamports.saturating_sub(new_account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, new_account.key, shortfall),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(new_account.key, &crate::id()),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
 /// Wrapper of `mint_new_edition_from_master_edition_via_token` instruction from `mpl_token_metadata` program
 #[inline(always)]
 pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
@@ -192,6 +309,27 @@ pub fn assert_keys_equal(key1: Pubkey, key2: Pubkey) -> Result<()> {
     }
 }
 
+/// Check creators list of `PrimaryMetadataCreators`
+pub fn assert_primary_metadata_creators(creators: &[Creator]) -> Result<()> {
+    if creators.len() > MAX_PRIMARY_CREATORS_LEN {
+        return Err(ErrorCode::CreatorsIsGtThanAvailable.into());
+    }
+
+    if creators.is_empty() {
+        return Err(ErrorCode::CreatorsIsEmpty.into());
+    }
+
+    let total_shares = creators
+        .iter()
+        .try_fold(0u8, |acc, c| acc.checked_add(c.share))
+        .ok_or(ErrorCode::CreatorsSharesIsInvalid)?;
+    if total_shares != 100 {
+        return Err(ErrorCode::CreatorsSharesIsInvalid.into());
+    }
+
+    Ok(())
+}
+
 pub fn calculate_primary_shares_for_creator(total_amount: u64, shares: u64) -> Result<u64> {
     Ok(total_amount
         .checked_mul(shares)
@@ -200,6 +338,24 @@ pub fn calculate_primary_shares_for_creator(total_amount: u64, shares: u64) -> R
         .ok_or(ErrorCode::MathOverflow)?)
 }
 
+/// Platform fee which is carved out of `Market::funds_collected` before any other share
+pub fn calculate_platform_fee(total_amount: u64, platform_fee_basis_points: u64) -> Result<u64> {
+    Ok(total_amount
+        .checked_mul(platform_fee_basis_points)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(MAX_BASIS_POINTS as u64)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+/// Referrer's share of a sale, which is carved out before creators and `Market` owner shares
+pub fn calculate_referral_share(amount: u64, referral_basis_points: u64) -> Result<u64> {
+    Ok(amount
+        .checked_mul(referral_basis_points)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(MAX_BASIS_POINTS as u64)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
 pub fn calculate_secondary_shares_for_creator(
     total_amount: u64,
     seller_fee_basis_points: u64,
@@ -230,3 +386,868 @@ pub fn calculate_secondary_shares_for_market_owner(
         )
         .ok_or(ErrorCode::MathOverflow)?)
 }
+
+/// Price of the piece sold after `supply` pieces at `now`, following presale price of `Market::allowlist`,
+/// `Market::price_tiers` or `Market::pricing_curve` if one is set
+pub fn calculate_market_price(market: &Market, supply: u64, now: u64) -> Result<u64> {
+    if let Some(allowlist) = &market.allowlist {
+        if is_presale(market, now) {
+            return Ok(allowlist.presale_price.unwrap_or(market.price));
+        }
+    }
+
+    if !market.price_tiers.is_empty() {
+        return Ok(market
+            .price_tiers
+            .iter()
+            .find(|tier| supply < tier.supply_threshold)
+            .map_or(market.price, |tier| tier.price));
+    }
+
+    let pricing_curve = match &market.pricing_curve {
+        Some(pricing_curve) => pricing_curve,
+        None => return Ok(market.price),
+    };
+
+    let decay_count = now
+        .saturating_sub(market.start_date)
+        .checked_div(pricing_curve.decay_interval)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(market
+        .price
+        .saturating_sub(decay_count.saturating_mul(pricing_curve.decay_step))
+        .max(pricing_curve.floor_price))
+}
+
+/// Price of `quantity` pieces sold after `supply` pieces at `now`, each piece is priced by its own tier
+pub fn calculate_market_amount(
+    market: &Market,
+    supply: u64,
+    quantity: u64,
+    now: u64,
+) -> Result<u64> {
+    let mut amount: u64 = 0;
+
+    for piece in 0..quantity {
+        let piece_supply = supply.checked_add(piece).ok_or(ErrorCode::MathOverflow)?;
+
+        amount = amount
+            .checked_add(calculate_market_price(market, piece_supply, now)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    Ok(amount)
+}
+
+/// Check, that price tiers have non-zero prices and strictly increasing supply thresholds
+pub fn assert_price_tiers(
+    price_tiers: &[PriceTier],
+    pricing_curve: &Option<PricingCurve>,
+) -> Result<()> {
+    if price_tiers.is_empty() {
+        return Ok(());
+    }
+
+    if pricing_curve.is_some() {
+        return Err(ErrorCode::PriceTiersWithPricingCurve.into());
+    }
+
+    if price_tiers.len() > MAX_PRICE_TIERS_LEN {
+        return Err(ErrorCode::InvalidPriceTiers.into());
+    }
+
+    let mut previous_threshold = 0;
+
+    for tier in price_tiers {
+        if tier.price == 0 {
+            return Err(ErrorCode::PriceIsZero.into());
+        }
+
+        if tier.supply_threshold <= previous_threshold {
+            return Err(ErrorCode::InvalidPriceTiers.into());
+        }
+
+        previous_threshold = tier.supply_threshold;
+    }
+
+    Ok(())
+}
+
+/// Check, that `Market` is in presale phase of its `Allowlist`
+pub fn is_presale(market: &Market, now: u64) -> bool {
+    match &market.allowlist {
+        Some(allowlist) => match allowlist.presale_start_date {
+            Some(presale_start_date) => presale_start_date <= now && now < market.start_date,
+            None => false,
+        },
+        None => false,
+    }
+}
+
+/// Leaf of the allowlist Merkle tree
+pub fn allowlist_leaf(wallet: &Pubkey, allocation: u64) -> [u8; 32] {
+    keccak::hashv(&[wallet.as_ref(), &allocation.to_le_bytes()]).0
+}
+
+/// Parent of two Merkle tree nodes, nodes are hashed in sorted order
+pub fn hash_merkle_nodes(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[a, b]).0
+    } else {
+        keccak::hashv(&[b, a]).0
+    }
+}
+
+/// Merkle proof check
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_merkle_nodes(&node, sibling));
+
+    computed_root == *root
+}
+
+/// Check, that `Market` accepts buys at `now`
+pub fn assert_market_is_live(market: &Market, now: u64) -> Result<()> {
+    if market.state == MarketState::Cancelled {
+        return Err(ErrorCode::MarketIsCancelled.into());
+    }
+
+    if market.state == MarketState::Paused {
+        return Err(ErrorCode::MarketIsPaused.into());
+    }
+
+    // Check, that `Market` is started
+    if !is_presale(market, now) && market.start_date > now {
+        return Err(ErrorCode::MarketIsNotStarted.into());
+    }
+
+    // Check, that `Market` is ended
+    if let Some(end_date) = market.end_date {
+        if now > end_date {
+            return Err(ErrorCode::MarketIsEnded.into());
+        }
+    } else if market.state == MarketState::Ended {
+        return Err(ErrorCode::MarketIsEnded.into());
+    }
+
+    Ok(())
+}
+
+/// Check, that `Market` is over and its funds can be withdrawn
+pub fn assert_market_is_ended(market: &Market, now: u64) -> Result<()> {
+    let is_paused = market.state == MarketState::Paused;
+
+    if let Some(end_date) = market.end_date {
+        // Paused `Market` is ended as soon as its `end_date` is passed
+        if now <= end_date {
+            return Err(if is_paused {
+                ErrorCode::MarketIsPaused.into()
+            } else {
+                ErrorCode::MarketInInvalidState.into()
+            });
+        }
+    } else if is_paused {
+        // Paused `Market` without `end_date` can only be resumed
+        return Err(ErrorCode::MarketIsPaused.into());
+    } else if market.state != MarketState::Ended {
+        return Err(ErrorCode::MarketInInvalidState.into());
+    }
+
+    Ok(())
+}
+
+/// Check, that `Market` starts in the future and doesn't end before its start
+pub fn assert_market_dates(start_date: u64, end_date: Option<u64>, now: u64) -> Result<()> {
+    // start_date cannot be in the past
+    if start_date < now {
+        return Err(ErrorCode::StartDateIsInPast.into());
+    }
+
+    // end_date should not be greater than start_date
+    if let Some(end_date) = end_date {
+        if start_date > end_date {
+            return Err(ErrorCode::EndDateIsEarlierThanBeginDate.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check, that `program_id` is SPL Token or Token-2022 program
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || *program_id == spl_token_2022::id()
+}
+
+/// Check, that treasury token accounts and `token_program` belong to the same token program
+pub fn assert_treasury_token_program(
+    treasury_mint: &AccountInfo,
+    treasury_holder: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<()> {
+    if !is_token_program(treasury_mint.owner)
+        || treasury_holder.owner != treasury_mint.owner
+        || token_program.key != treasury_mint.owner
+    {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    Ok(())
+}
+
+/// Token amount of SPL Token or Token-2022 account
+pub fn token_account_amount(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.data.borrow();
+    let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+
+    Ok(token_account.base.amount)
+}
+
+/// Associated token account address of `wallet` for mint of `token_program`
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &anchor_spl::associated_token::ID,
+    )
+    .0
+}
+
+/// Check treasury accounts of `Market` and fund native treasury holder
+pub fn init_treasury<'a>(
+    treasury_mint: &AccountInfo<'a>,
+    treasury_holder: &AccountInfo<'a>,
+    treasury_owner: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+) -> Result<()> {
+    let is_native = treasury_mint.key() == System::id();
+
+    if !is_native {
+        if !is_token_program(treasury_mint.owner) || treasury_holder.owner != treasury_mint.owner {
+            return Err(ProgramError::IllegalOwner.into());
+        }
+
+        if token::accessor::mint(treasury_holder)? != *treasury_mint.key {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        if token::accessor::authority(treasury_holder)? != treasury_owner.key() {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+    } else {
+        // for native SOL we use PDA as a treasury holder
+        // because of security reasons(only program can spend this SOL)
+        if treasury_holder.key != treasury_owner.key {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        // Treasury owner is derived per `Market`, so lamports sent to it in advance
+        // only reduce the amount needed to keep it rent exempt
+        let shortfall = MINIMUM_BALANCE_FOR_SYSTEM_ACCS.saturating_sub(treasury_holder.lamports());
+
+        // we need fund treasury holder account such as it will hold some metadata with SOL balance
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, treasury_holder.key, shortfall),
+                &[payer.clone(), treasury_holder.clone()],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Return rent of `Market` treasury replaced before the first sale to `destination`.
+/// Native treasury owner is drained, token treasury holder is closed, so it has to be empty
+pub fn close_treasury<'a>(
+    market: &Market,
+    market_key: &Pubkey,
+    treasury_holder: &AccountInfo<'a>,
+    treasury_owner: &AccountInfo<'a>,
+    token_program: Option<&AccountInfo<'a>>,
+    destination: &AccountInfo<'a>,
+) -> Result<()> {
+    assert_keys_equal(treasury_holder.key(), market.treasury_holder)?;
+    assert_keys_equal(treasury_owner.key(), market.treasury_owner)?;
+
+    let signer_seeds: &[&[u8]] = &[
+        HOLDER_PREFIX.as_bytes(),
+        market.treasury_mint.as_ref(),
+        market.selling_resource.as_ref(),
+        market_key.as_ref(),
+        &[market.treasury_owner_bump],
+    ];
+
+    if market.treasury_mint == System::id() {
+        return sys_transfer(
+            treasury_holder,
+            destination,
+            treasury_holder.lamports(),
+            signer_seeds,
+        );
+    }
+
+    let token_program = token_program.ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+    if !is_token_program(token_program.key) || treasury_holder.owner != token_program.key {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    if token::accessor::amount(treasury_holder)? != 0 {
+        return Err(ErrorCode::TreasuryIsNotEmpty.into());
+    }
+
+    invoke_signed(
+        &spl_token_2022::instruction::close_account(
+            token_program.key,
+            treasury_holder.key,
+            destination.key,
+            treasury_owner.key,
+            &[],
+        )?,
+        &[
+            treasury_holder.clone(),
+            destination.clone(),
+            treasury_owner.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Creators paid from `Market` funds: `PrimaryMetadataCreators` until primary sale, metadata creators after it
+pub fn payout_creators(
+    metadata_key: &Pubkey,
+    metadata: &mpl_token_metadata::state::Metadata,
+    primary_metadata_creators: Option<&AccountInfo>,
+) -> Result<Vec<Creator>> {
+    let metadata_creators = || {
+        metadata
+            .data
+            .creators
+            .as_ref()
+            .map(|creators| {
+                creators
+                    .iter()
+                    .map(|item| Creator::from(item.clone()))
+                    .collect::<Vec<Creator>>()
+            })
+            .unwrap_or_default()
+    };
+
+    if metadata.primary_sale_happened {
+        return Ok(metadata_creators());
+    }
+
+    let primary_metadata_creators =
+        primary_metadata_creators.ok_or(ErrorCode::PrimaryMetadataCreatorsNotProvided)?;
+
+    assert_derivation(
+        &crate::id(),
+        primary_metadata_creators,
+        &[
+            PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(),
+            metadata_key.as_ref(),
+        ],
+    )?;
+    let primary_metadata_creators =
+        Account::<PrimaryMetadataCreators>::try_from(primary_metadata_creators)?;
+
+    // Empty list was created by a sale without saved creators
+    if primary_metadata_creators.creators.is_empty() {
+        Ok(metadata_creators())
+    } else {
+        Ok(primary_metadata_creators.creators.clone())
+    }
+}
+
+/// Every creator in metadata order with his share of `Market` funds, followed by `Market` owner
+/// after primary sale if he isn't one of the creators
+pub fn market_payees(
+    market: &Market,
+    metadata: &mpl_token_metadata::state::Metadata,
+    creators: &[Creator],
+) -> Result<Vec<(Pubkey, u64, PayoutRole)>> {
+    // Creators and `Market` owner share what is left after the platform fee and referrals
+    let funds_collected = market
+        .funds_collected
+        .checked_sub(calculate_platform_fee(
+            market.funds_collected,
+            market.platform_fee_basis_points as u64,
+        )?)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_sub(market.referral_funds)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let seller_fee_basis_points = metadata.data.seller_fee_basis_points as u64;
+
+    let mut payees: Vec<(Pubkey, u64, PayoutRole)> = Vec::new();
+    for creator in creators.iter() {
+        let payee = if !metadata.primary_sale_happened {
+            (
+                creator.address,
+                calculate_primary_shares_for_creator(funds_collected, creator.share as u64)?,
+                PayoutRole::Creator,
+            )
+        } else {
+            let creator_share = calculate_secondary_shares_for_creator(
+                funds_collected,
+                seller_fee_basis_points,
+                creator.share as u64,
+            )?;
+
+            if creator.address == market.owner {
+                let market_owner_share = calculate_secondary_shares_for_market_owner(
+                    funds_collected,
+                    seller_fee_basis_points,
+                )?;
+
+                (
+                    creator.address,
+                    creator_share
+                        .checked_add(market_owner_share)
+                        .ok_or(ErrorCode::MathOverflow)?,
+                    PayoutRole::CreatorAndMarketOwner,
+                )
+            } else {
+                (creator.address, creator_share, PayoutRole::Creator)
+            }
+        };
+
+        payees.push(payee);
+    }
+
+    if metadata.primary_sale_happened && !creators.iter().any(|c| c.address == market.owner) {
+        payees.push((
+            market.owner,
+            calculate_secondary_shares_for_market_owner(funds_collected, seller_fee_basis_points)?,
+            PayoutRole::MarketOwner,
+        ));
+    }
+
+    Ok(payees)
+}
+
+/// Funds of `Ended` `Market` which are not withdrawn yet: unpaid shares, platform fee and referrals.
+/// Rounding remainder of the shares isn't owed to anyone
+pub fn calculate_owed_funds(
+    market: &Market,
+    metadata: &mpl_token_metadata::state::Metadata,
+    creators: &[Creator],
+) -> Result<u64> {
+    let mut owed = market
+        .referral_funds
+        .checked_sub(market.referral_funds_withdrawn)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if !market.platform_fee_withdrawn {
+        owed = owed
+            .checked_add(calculate_platform_fee(
+                market.funds_collected,
+                market.platform_fee_basis_points as u64,
+            )?)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    for (payee, amount, _) in market_payees(market, metadata, creators)? {
+        if !market.paid_out.contains(&payee) {
+            owed = owed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+
+    Ok(owed)
+}
+
+/// Check, that `wallet` can buy `quantity` more pieces at `Market`
+pub fn assert_buy_limits(
+    market: &Market,
+    trade_history: &TradeHistory,
+    wallet: &Pubkey,
+    quantity: u64,
+    allowlist_proof: Option<AllowlistProof>,
+    now: u64,
+) -> Result<()> {
+    let already_bought = trade_history
+        .already_bought
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Check, that user not reach buy limit
+    if let Some(pieces_in_one_wallet) = market.pieces_in_one_wallet {
+        if already_bought > pieces_in_one_wallet {
+            return Err(ErrorCode::UserReachBuyLimit.into());
+        }
+    }
+
+    // Check, that user is allowlisted and not reach his allocation
+    if let Some(allowlist) = &market.allowlist {
+        if is_presale(market, now) || allowlist.presale_start_date.is_none() {
+            let allowlist_proof = allowlist_proof.ok_or(ErrorCode::AllowlistProofNotProvided)?;
+
+            if !verify_merkle_proof(
+                &allowlist_proof.proof,
+                &allowlist.merkle_root,
+                allowlist_leaf(wallet, allowlist_proof.allocation),
+            ) {
+                return Err(ErrorCode::InvalidAllowlistProof.into());
+            }
+
+            if already_bought > allowlist_proof.allocation {
+                return Err(ErrorCode::UserReachBuyLimit.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check, that buyer holds a token passing `Market::gate` and count `quantity` against its limit
+pub fn assert_gate<'a>(
+    market: &Market,
+    market_key: &Pubkey,
+    user_wallet: &AccountInfo<'a>,
+    gate_token_account: Option<&AccountInfo<'a>>,
+    gate_metadata: Option<&AccountInfo<'a>>,
+    gate_usage: Option<&AccountInfo<'a>>,
+    system_program: &AccountInfo<'a>,
+    quantity: u64,
+) -> Result<()> {
+    let gate = match &market.gate {
+        Some(gate) => gate,
+        None => return Ok(()),
+    };
+
+    let gate_token_account = gate_token_account.ok_or(ErrorCode::GateAccountsNotProvided)?;
+
+    // Check, that buyer holds the gate token
+    if !is_token_program(gate_token_account.owner) {
+        return Err(ErrorCode::GateTokenIsInvalid.into());
+    }
+
+    let gate_mint = {
+        let data = gate_token_account.data.borrow();
+        let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+
+        if token_account.base.owner != user_wallet.key() || token_account.base.amount == 0 {
+            return Err(ErrorCode::GateTokenIsInvalid.into());
+        }
+
+        token_account.base.mint
+    };
+
+    match gate.kind {
+        GateKind::Mint => {
+            if gate_mint != gate.key {
+                return Err(ErrorCode::GateTokenIsInvalid.into());
+            }
+        }
+        GateKind::Collection => {
+            let gate_metadata = gate_metadata.ok_or(ErrorCode::GateAccountsNotProvided)?;
+
+            assert_derivation(
+                &mpl_token_metadata::id(),
+                gate_metadata,
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    gate_mint.as_ref(),
+                ],
+            )?;
+
+            let metadata = mpl_token_metadata::state::Metadata::from_account_info(gate_metadata)?;
+
+            match metadata.collection {
+                Some(collection) if collection.verified && collection.key == gate.key => {}
+                _ => return Err(ErrorCode::GateTokenIsInvalid.into()),
+            }
+        }
+    }
+
+    // Check, that gate token not reach its limit, whichever wallet used it before
+    if let Some(limit) = gate.limit {
+        let gate_usage = gate_usage.ok_or(ErrorCode::GateAccountsNotProvided)?;
+
+        let gate_usage_bump = assert_derivation(
+            &crate::id(),
+            gate_usage,
+            &[
+                GATE_USAGE_PREFIX.as_bytes(),
+                market_key.as_ref(),
+                gate_mint.as_ref(),
+            ],
+        )?;
+
+        let mut usage = if gate_usage.data_is_empty() {
+            sys_create_pda(
+                user_wallet,
+                gate_usage,
+                system_program,
+                GateUsage::LEN,
+                &[
+                    GATE_USAGE_PREFIX.as_bytes(),
+                    market_key.as_ref(),
+                    gate_mint.as_ref(),
+                    &[gate_usage_bump],
+                ],
+            )?;
+
+            GateUsage {
+                market: *market_key,
+                gate_mint,
+                bought: 0,
+            }
+        } else {
+            GateUsage::try_deserialize(&mut gate_usage.data.borrow().as_ref())?
+        };
+
+        usage.bought = usage
+            .bought
+            .checked_add(quantity)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        if usage.bought > limit {
+            return Err(ErrorCode::GateTokenReachBuyLimit.into());
+        }
+
+        let mut data = gate_usage.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        usage.try_serialize(&mut writer)?;
+    }
+
+    Ok(())
+}
+
+/// Transfer `amount` from the buyer to `Market` treasury and return what the treasury received
+pub fn transfer_to_treasury<'a>(
+    market: &Market,
+    user_token_account: &AccountInfo<'a>,
+    user_wallet: &AccountInfo<'a>,
+    treasury_holder: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    treasury_mint: Option<&AccountInfo<'a>>,
+    treasury_token_program: Option<&AccountInfo<'a>>,
+    amount: u64,
+) -> Result<u64> {
+    let is_native = market.treasury_mint == System::id();
+
+    if !is_native {
+        let balance_before = token_account_amount(treasury_holder)?;
+
+        if *treasury_holder.owner == spl_token_2022::id() {
+            let treasury_mint = treasury_mint.ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+            let treasury_token_program =
+                treasury_token_program.ok_or(ErrorCode::TreasuryAccountsNotProvided)?;
+
+            assert_keys_equal(treasury_mint.key(), market.treasury_mint)?;
+            assert_treasury_token_program(treasury_mint, treasury_holder, treasury_token_program)?;
+
+            spl_transfer_checked(
+                treasury_token_program,
+                user_token_account,
+                treasury_mint,
+                treasury_holder,
+                user_wallet,
+                amount,
+                &[],
+            )?;
+        } else {
+            let cpi_program = token_program.clone();
+            let cpi_accounts = token::Transfer {
+                from: user_token_account.clone(),
+                to: treasury_holder.clone(),
+                authority: user_wallet.clone(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        // Transfer fee of Token-2022 mint is withheld, so treasury gets less than `amount`
+        Ok(token_account_amount(treasury_holder)?
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::MathOverflow)?)
+    } else {
+        if user_token_account.key != user_wallet.key {
+            return Err(ErrorCode::UserWalletMustMatchUserTokenAccount.into());
+        }
+
+        invoke(
+            // For native SOL transfer user_wallet key == user_token_account key
+            &system_instruction::transfer(user_token_account.key, treasury_holder.key, amount),
+            &[user_token_account.clone(), treasury_holder.clone()],
+        )?;
+
+        Ok(amount)
+    }
+}
+
+/// Transfer `amount` out of `Market` treasury, signed by treasury owner
+pub fn transfer_from_treasury<'a>(
+    treasury_holder: &AccountInfo<'a>,
+    treasury_mint: &AccountInfo<'a>,
+    treasury_owner: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    spl_transfer_checked(
+        token_program,
+        treasury_holder,
+        treasury_mint,
+        destination,
+        treasury_owner,
+        amount,
+        signer_seeds,
+    )
+}
+
+/// Update `Market`, `SellingResource` and `TradeHistory` counters after `quantity` pieces were sold for `amount`
+pub fn record_sale(
+    market: &mut Market,
+    selling_resource: &mut SellingResource,
+    trade_history: &mut TradeHistory,
+    quantity: u64,
+    amount: u64,
+) -> Result<()> {
+    market.funds_collected = market
+        .funds_collected
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    trade_history.funds_paid = trade_history
+        .funds_paid
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    trade_history.already_bought = trade_history
+        .already_bought
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    selling_resource.supply = selling_resource
+        .supply
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    market.sold = market
+        .sold
+        .checked_add(quantity)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Check, that `Market` doesn't sell more than its allocation
+    if let Some(allocation) = market.allocation {
+        match market.sold.cmp(&allocation) {
+            Ordering::Greater => return Err(ErrorCode::AllocationIsExhausted.into()),
+            Ordering::Equal => market.state = MarketState::Ended,
+            Ordering::Less => (),
+        }
+    }
+
+    // Check, that `SellingResource::max_supply` is not overflowed by `supply`
+    if let Some(max_supply) = selling_resource.max_supply {
+        match selling_resource.supply.cmp(&max_supply) {
+            Ordering::Greater => return Err(ErrorCode::SupplyIsGtThanMaxSupply.into()),
+            Ordering::Equal => {
+                selling_resource.state = SellingResourceState::Exhausted;
+                market.state = MarketState::Ended;
+            }
+            Ordering::Less => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Lock `PrimaryMetadataCreators` of sold `resource`, so `withdraw` pays the creators buyers paid to.
+/// Sale without saved creators creates an empty locked list, so creators can't be saved after sales
+pub fn lock_primary_metadata_creators<'a>(
+    primary_metadata_creators: &AccountInfo<'a>,
+    resource: &Pubkey,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let bump = assert_derivation(
+        &crate::id(),
+        primary_metadata_creators,
+        &[
+            PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(),
+            metadata.as_ref(),
+        ],
+    )?;
+
+    // Empty list means metadata creators are paid
+    if primary_metadata_creators.data_is_empty() {
+        sys_create_pda(
+            payer,
+            primary_metadata_creators,
+            system_program,
+            PrimaryMetadataCreators::LEN,
+            &[
+                PRIMARY_METADATA_CREATORS_PREFIX.as_bytes(),
+                metadata.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        let creators = PrimaryMetadataCreators {
+            creators: Vec::new(),
+            locked: true,
+        };
+
+        let mut data = primary_metadata_creators.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        creators.try_serialize(&mut writer)?;
+
+        return Ok(());
+    }
+
+    let mut creators = PrimaryMetadataCreators::try_deserialize(
+        &mut primary_metadata_creators.data.borrow().as_ref(),
+    )?;
+
+    if !creators.locked {
+        creators.locked = true;
+
+        let mut data = primary_metadata_creators.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        creators.try_serialize(&mut writer)?;
+    }
+
+    Ok(())
+}
+
+/// Accrue referrer's share of sale `amount` paid by `wallet`
+pub fn accrue_referral(
+    market: &mut Market,
+    referral: &mut Referral,
+    wallet: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if referral.referrer == *wallet {
+        return Err(ErrorCode::ReferrerIsBuyer.into());
+    }
+
+    let share = calculate_referral_share(amount, market.referral_basis_points as u64)?;
+
+    referral.funds_accrued = referral
+        .funds_accrued
+        .checked_add(share)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    market.referral_funds = market
+        .referral_funds
+        .checked_add(share)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
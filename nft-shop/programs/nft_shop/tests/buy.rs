@@ -1,16 +1,23 @@
 use anchor_client::solana_sdk::transaction::Transaction;
 use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
-use anchor_lang::prelude::Clock;
+use anchor_lang::prelude::{AccountMeta, Clock};
 use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
 use anchor_lang::solana_program::{system_program, sysvar};
 use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 use anchor_lang::{InstructionData, ToAccountMetas};
 use anchor_spl::token::spl_token;
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::EditionBought;
 use nft_shop::pda::*;
-use nft_shop::state::{SellingResource, TradeHistory};
+use nft_shop::state::{
+    Allowlist, AllowlistProof, Gate, GateKind, GateUsage, Market, PricingCurve, SellingResource,
+    TradeHistory,
+};
+use nft_shop::utils::{allowlist_leaf, hash_merkle_nodes};
 use solana_program_test::tokio;
+use spl_token_2022::extension::StateWithExtensions;
 use std::time::SystemTime;
 
 mod utils;
@@ -63,9 +70,10 @@ async fn buy_success() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -89,7 +97,6 @@ async fn buy_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -97,6 +104,12 @@ async fn buy_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -144,9 +157,9 @@ async fn buy_success() {
     let selling_resource =
         SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
 
-    let (trade_history, trade_history_bump) =
+    let (trade_history, _) =
         find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
-    let (vault_owner, vault_owner_bump) =
+    let (vault_owner, _) =
         find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
 
     let user_wallet = context.payer.dirty_clone();
@@ -238,8 +251,7 @@ async fn buy_success() {
     );
 
     let data = nft_shop::instruction::Buy {
-        _trade_history_bump: trade_history_bump,
-        vault_owner_bump,
+        allowlist_proof: None,
     };
 
     let accounts = nft_shop::accounts::Buy {
@@ -263,6 +275,13 @@ async fn buy_success() {
         token_metadata_program: mpl_token_metadata::id(),
         token_program: spl_token::id(),
         system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
     };
 
     let ix = Instruction {
@@ -277,7 +296,7 @@ async fn buy_success() {
         &[&user_wallet],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let selling_resource_account = context
         .banks_client
@@ -299,10 +318,19 @@ async fn buy_success() {
 
     assert_eq!(selling_resource_data.supply, 1);
     assert_eq!(trade_history_data.already_bought, 1);
+
+    let events = find_events::<EditionBought>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(events[0].buyer, user_wallet.pubkey());
+    assert_eq!(events[0].new_mint, new_mint_keypair.pubkey());
+    assert_eq!(events[0].edition, 1);
+    assert_eq!(events[0].price, price);
 }
 
 #[tokio::test]
-async fn failure_buy_market_is_not_started() {
+async fn buy_token_2022_with_transfer_fee_success() {
     let mut context = nft_shop_program_test().start_with_context().await;
 
     let (store_admin, store_keypair) = create_store(&mut context).await;
@@ -339,22 +367,26 @@ async fn failure_buy_market_is_not_started() {
 
     let market_keypair = Keypair::new();
 
+    // 1% of every transfer is withheld by Token-2022 mint
     let treasury_mint_keypair = Keypair::new();
-    create_mint(
+    create_mint_with_transfer_fee(
         &mut context,
         &treasury_mint_keypair,
         &store_admin.pubkey(),
         0,
+        100,
+        u64::MAX,
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
-    create_token_account(
+    create_token_2022_account(
         &mut context,
         &treasury_holder_keypair,
         &treasury_mint_keypair.pubkey(),
@@ -365,8 +397,7 @@ async fn failure_buy_market_is_not_started() {
     let start_date = (SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .expect("Time went backwards")
-        .as_secs()
-        + 60) as u64;
+        .as_secs()) as u64;
 
     let market_name = "1234_1234_".to_string();
     let market_description = "1234_1234_1234_1234_".to_string();
@@ -375,7 +406,6 @@ async fn failure_buy_market_is_not_started() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -383,6 +413,12 @@ async fn failure_buy_market_is_not_started() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -414,6 +450,10 @@ async fn failure_buy_market_is_not_started() {
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
     // Buy
     let selling_resource_data = context
         .banks_client
@@ -426,22 +466,22 @@ async fn failure_buy_market_is_not_started() {
     let selling_resource =
         SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
 
-    let (trade_history, trade_history_bump) =
+    let (trade_history, _) =
         find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
-    let (vault_owner, vault_owner_bump) =
+    let (vault_owner, _) =
         find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
 
     let user_wallet = context.payer.dirty_clone();
 
     let user_token_account = Keypair::new();
-    create_token_account(
+    create_token_2022_account(
         &mut context,
         &user_token_account,
         &treasury_mint_keypair.pubkey(),
         &user_wallet.pubkey(),
     )
     .await;
-    mint_to(
+    mint_to_token_2022(
         &mut context,
         &treasury_mint_keypair.pubkey(),
         &user_token_account.pubkey(),
@@ -520,8 +560,7 @@ async fn failure_buy_market_is_not_started() {
     );
 
     let data = nft_shop::instruction::Buy {
-        _trade_history_bump: trade_history_bump,
-        vault_owner_bump,
+        allowlist_proof: None,
     };
 
     let accounts = nft_shop::accounts::Buy {
@@ -545,6 +584,13 @@ async fn failure_buy_market_is_not_started() {
         token_metadata_program: mpl_token_metadata::id(),
         token_program: spl_token::id(),
         system_program: system_program::id(),
+        referral: None,
+        treasury_mint: Some(treasury_mint_keypair.pubkey()),
+        treasury_token_program: Some(spl_token_2022::id()),
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
     };
 
     let ix = Instruction {
@@ -559,18 +605,50 @@ async fn failure_buy_market_is_not_started() {
         &[&user_wallet],
         context.last_blockhash,
     );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
-    let tx_error = context
+    let market_account = context
         .banks_client
-        .process_transaction(tx)
+        .get_account(market_keypair.pubkey())
         .await
-        .unwrap_err();
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
 
-    assert_error(tx_error, ErrorCode::MarketIsNotStarted.into());
+    let treasury_holder_account = context
+        .banks_client
+        .get_account(treasury_holder_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let treasury_holder_data = StateWithExtensions::<spl_token_2022::state::Account>::unpack(
+        &treasury_holder_account.data,
+    )
+    .unwrap();
+
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let received = price - price / 100;
+
+    assert_eq!(treasury_holder_data.base.amount, received);
+    assert_eq!(market_data.funds_collected, received);
+    assert_eq!(trade_history_data.funds_paid, received);
+
+    let events = find_events::<EditionBought>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].price, price);
 }
 
 #[tokio::test]
-async fn failure_buy_market_is_ended() {
+async fn buy_dutch_auction_success() {
     let mut context = nft_shop_program_test().start_with_context().await;
 
     let (store_admin, store_keypair) = create_store(&mut context).await;
@@ -616,9 +694,10 @@ async fn failure_buy_market_is_ended() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -630,31 +709,34 @@ async fn failure_buy_market_is_ended() {
     )
     .await;
 
-    let start_date = (SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()) as u64;
-    let end_date = (SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()
-        + 1) as u64;
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let start_date = clock.unix_timestamp as u64 + 1;
 
     let market_name = "1234_1234_".to_string();
     let market_description = "1234_1234_1234_1234_".to_string();
     let mutable = true;
     let price = 2 * ONE_SOL;
     let pieces_in_one_wallet = Some(1);
+    let pricing_curve = PricingCurve {
+        floor_price: ONE_SOL,
+        decay_interval: 60,
+        decay_step: ONE_SOL / 4,
+    };
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
         price,
         pieces_in_one_wallet,
         start_date: start_date as u64,
-        end_date: Some(end_date),
+        end_date: None,
+        pricing_curve: Some(pricing_curve.clone()),
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -686,9 +768,9 @@ async fn failure_buy_market_is_ended() {
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Waiting for Market`s start and ending
-    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
-    context.warp_to_slot(clock.slot + 1500).unwrap();
+    // Two price drops since Market`s start
+    clock.unix_timestamp = (start_date + 2 * pricing_curve.decay_interval) as i64;
+    context.set_sysvar(&clock);
 
     // Buy
     let selling_resource_data = context
@@ -702,9 +784,9 @@ async fn failure_buy_market_is_ended() {
     let selling_resource =
         SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
 
-    let (trade_history, trade_history_bump) =
+    let (trade_history, _) =
         find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
-    let (vault_owner, vault_owner_bump) =
+    let (vault_owner, _) =
         find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
 
     let user_wallet = context.payer.dirty_clone();
@@ -796,8 +878,7 @@ async fn failure_buy_market_is_ended() {
     );
 
     let data = nft_shop::instruction::Buy {
-        _trade_history_bump: trade_history_bump,
-        vault_owner_bump,
+        allowlist_proof: None,
     };
 
     let accounts = nft_shop::accounts::Buy {
@@ -821,6 +902,13 @@ async fn failure_buy_market_is_ended() {
         token_metadata_program: mpl_token_metadata::id(),
         token_program: spl_token::id(),
         system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
     };
 
     let ix = Instruction {
@@ -835,12 +923,2428 @@ async fn failure_buy_market_is_ended() {
         &[&user_wallet],
         context.last_blockhash,
     );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
-    let tx_error = context
+    let selling_resource_account = context
         .banks_client
-        .process_transaction(tx)
+        .get_account(selling_resource_keypair.pubkey())
         .await
-        .unwrap_err();
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
 
-    assert_error(tx_error, ErrorCode::MarketIsEnded.into());
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    let user_token_account_data = context
+        .banks_client
+        .get_account(user_token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let user_token_account_data =
+        spl_token::state::Account::unpack_from_slice(user_token_account_data.as_ref()).unwrap();
+
+    let expected_price = price - 2 * pricing_curve.decay_step;
+
+    assert_eq!(selling_resource_data.supply, 1);
+    assert_eq!(trade_history_data.already_bought, 1);
+    assert_eq!(market_data.funds_collected, expected_price);
+    assert_eq!(user_token_account_data.amount, price - expected_price);
+}
+
+#[tokio::test]
+async fn buy_presale_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let presale_start_date = clock.unix_timestamp as u64 + 1;
+    let start_date = presale_start_date + 3600;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+    let presale_price = ONE_SOL;
+
+    let allocation = 1;
+    let user_leaf = allowlist_leaf(&context.payer.pubkey(), allocation);
+    let other_leaf = allowlist_leaf(&Pubkey::new_unique(), allocation);
+    let allowlist = Allowlist {
+        merkle_root: hash_merkle_nodes(&user_leaf, &other_leaf),
+        presale_start_date: Some(presale_start_date),
+        presale_price: Some(presale_price),
+    };
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: Some(allowlist),
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for presale start
+    clock.unix_timestamp = (presale_start_date + 1) as i64;
+    context.set_sysvar(&clock);
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: Some(AllowlistProof {
+            allocation,
+            proof: vec![other_leaf],
+        }),
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    let user_token_account_data = context
+        .banks_client
+        .get_account(user_token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let user_token_account_data =
+        spl_token::state::Account::unpack_from_slice(user_token_account_data.as_ref()).unwrap();
+
+    let expected_price = presale_price;
+
+    assert_eq!(selling_resource_data.supply, 1);
+    assert_eq!(trade_history_data.already_bought, 1);
+    assert_eq!(market_data.funds_collected, expected_price);
+    assert_eq!(user_token_account_data.amount, price - expected_price);
+}
+
+#[tokio::test]
+async fn failure_buy_market_is_not_started() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+        + 60) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsNotStarted.into());
+}
+
+#[tokio::test]
+async fn failure_buy_market_is_ended() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+    let end_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+        + 1) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: Some(end_date),
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start and ending
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsEnded.into());
+}
+
+#[tokio::test]
+async fn failure_buy_market_is_paused() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // PauseMarket
+    let accounts = nft_shop::accounts::PauseMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        authority: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = nft_shop::instruction::PauseMarket {}.data();
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsPaused.into());
+}
+
+#[tokio::test]
+async fn failure_buy_invalid_allowlist_proof() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let presale_start_date = clock.unix_timestamp as u64 + 1;
+    let start_date = presale_start_date + 3600;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+    let presale_price = ONE_SOL;
+
+    let allocation = 1;
+    let user_leaf = allowlist_leaf(&context.payer.pubkey(), allocation);
+    let other_leaf = allowlist_leaf(&Pubkey::new_unique(), allocation);
+    let allowlist = Allowlist {
+        merkle_root: hash_merkle_nodes(&user_leaf, &other_leaf),
+        presale_start_date: Some(presale_start_date),
+        presale_price: Some(presale_price),
+    };
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: Some(allowlist),
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for presale start
+    clock.unix_timestamp = (presale_start_date + 1) as i64;
+    context.set_sysvar(&clock);
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: Some(AllowlistProof {
+            allocation: allocation + 1,
+            proof: vec![other_leaf],
+        }),
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::InvalidAllowlistProof.into());
+}
+
+#[tokio::test]
+async fn buy_gated_by_collection_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // Collection and its verified member held by the buyer
+    let gate_owner = context.payer.dirty_clone();
+    let collection = create_nft(&mut context, gate_owner.dirty_clone(), None, Some(0), true)
+        .await
+        .unwrap();
+    let gate_nft = create_nft(&mut context, gate_owner, None, Some(0), true)
+        .await
+        .unwrap();
+    verify_collection(&mut context, &gate_nft, &collection)
+        .await
+        .unwrap();
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: Some(Gate {
+            kind: GateKind::Collection,
+            key: collection.mint.pubkey(),
+            limit: Some(1),
+        }),
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (gate_usage, _) =
+        find_gate_usage_address(&market_keypair.pubkey(), &gate_nft.mint.pubkey());
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: Some(gate_nft.ata),
+        gate_metadata: Some(gate_nft.metadata),
+        gate_usage: Some(gate_usage),
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let gate_usage_account = context
+        .banks_client
+        .get_account(gate_usage)
+        .await
+        .unwrap()
+        .unwrap();
+    let gate_usage_data =
+        GateUsage::try_deserialize(&mut gate_usage_account.data.as_ref()).unwrap();
+
+    assert_eq!(selling_resource_data.supply, 1);
+    assert_eq!(gate_usage_data.market, market_keypair.pubkey());
+    assert_eq!(gate_usage_data.gate_mint, gate_nft.mint.pubkey());
+    assert_eq!(gate_usage_data.bought, 1);
+}
+
+#[tokio::test]
+async fn failure_buy_gate_token_reach_buy_limit() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // Gate token held by the buyer
+    let gate_owner = context.payer.dirty_clone();
+    let gate_nft = create_nft(&mut context, gate_owner, None, Some(0), true)
+        .await
+        .unwrap();
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let price = 2 * ONE_SOL;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: "1234_1234_".to_string(),
+        description: "1234_1234_1234_1234_".to_string(),
+        mutable: true,
+        price,
+        pieces_in_one_wallet: None,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: Some(Gate {
+            kind: GateKind::Mint,
+            key: gate_nft.mint.pubkey(),
+            limit: Some(1),
+        }),
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // `GateUsage` can be funded by anyone before the first buy
+    let (gate_usage, _) =
+        find_gate_usage_address(&market_keypair.pubkey(), &gate_nft.mint.pubkey());
+    airdrop(&mut context, &gate_usage, ONE_SOL).await.unwrap();
+
+    // Buy setup
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        2 * price,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    // (new_metadata, new_edition, new_mint, new_token_account, edition_marker)
+    let buy_accounts = |edition_accounts: &[AccountMeta]| nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata: edition_accounts[0].pubkey,
+        new_edition: edition_accounts[1].pubkey,
+        master_edition,
+        new_mint: edition_accounts[2].pubkey,
+        edition_marker: edition_accounts[4].pubkey,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: edition_accounts[3].pubkey,
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: Some(gate_nft.ata),
+        gate_metadata: None,
+        gate_usage: Some(gate_usage),
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    // Buy
+
+    let edition_accounts =
+        create_edition_accounts(&mut context, &user_wallet, &selling_resource.resource, 1).await;
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: buy_accounts(&edition_accounts).to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Buy with the same gate token
+
+    let edition_accounts =
+        create_edition_accounts(&mut context, &user_wallet, &selling_resource.resource, 2).await;
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: buy_accounts(&edition_accounts).to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::GateTokenReachBuyLimit.into());
+}
+
+#[tokio::test]
+async fn failure_buy_gate_token_is_invalid() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // Token which doesn't pass the gate
+    let gate_owner = context.payer.dirty_clone();
+    let gate_nft = create_nft(&mut context, gate_owner, None, Some(0), true)
+        .await
+        .unwrap();
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: Some(Gate {
+            kind: GateKind::Mint,
+            key: Pubkey::new_unique(),
+            limit: None,
+        }),
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: Some(gate_nft.ata),
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::GateTokenIsInvalid.into());
 }
@@ -0,0 +1,508 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::prelude::Clock;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use nft_minter::utils::Creator;
+use nft_shop::error::ErrorCode;
+use nft_shop::events::FungibleBought;
+use nft_shop::pda::*;
+use nft_shop::state::{SellingResource, TradeHistory};
+use solana_program_test::tokio;
+use std::time::SystemTime;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn buy_fungible_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let amount = 10;
+
+    let (selling_resource_keypair, vault, _) = init_fungible_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        amount,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2;
+    let pieces_in_one_wallet = Some(5);
+    let quantity = 3;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // BuyFungible
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price * quantity,
+    )
+    .await;
+
+    let destination = Keypair::new();
+    create_token_account(
+        &mut context,
+        &destination,
+        &selling_resource.resource,
+        &user_wallet.pubkey(),
+    )
+    .await;
+
+    let data = nft_shop::instruction::BuyFungible {
+        quantity,
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::BuyFungible {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        vault: vault.pubkey(),
+        vault_owner,
+        destination: destination.pubkey(),
+        system_program: system_program::id(),
+        clock: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let destination_account = context
+        .banks_client
+        .get_account(destination.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let destination_data =
+        spl_token::state::Account::unpack_from_slice(&destination_account.data).unwrap();
+
+    let vault_account = context
+        .banks_client
+        .get_account(vault.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let vault_data = spl_token::state::Account::unpack_from_slice(&vault_account.data).unwrap();
+
+    assert_eq!(selling_resource_data.supply, quantity);
+    assert_eq!(trade_history_data.already_bought, quantity);
+    assert_eq!(trade_history_data.funds_paid, price * quantity);
+    assert_eq!(destination_data.amount, quantity);
+    assert_eq!(vault_data.amount, amount - quantity);
+
+    let events = find_events::<FungibleBought>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(events[0].buyer, user_wallet.pubkey());
+    assert_eq!(events[0].quantity, quantity);
+    assert_eq!(events[0].price, price);
+}
+
+#[tokio::test]
+async fn failure_buy_fungible_user_reach_buy_limit() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let amount = 10;
+
+    let (selling_resource_keypair, vault, _) = init_fungible_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        amount,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2;
+    let pieces_in_one_wallet = Some(2);
+    let quantity = 3;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // BuyFungible
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price * quantity,
+    )
+    .await;
+
+    let destination = Keypair::new();
+    create_token_account(
+        &mut context,
+        &destination,
+        &selling_resource.resource,
+        &user_wallet.pubkey(),
+    )
+    .await;
+
+    let data = nft_shop::instruction::BuyFungible {
+        quantity,
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::BuyFungible {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        vault: vault.pubkey(),
+        vault_owner,
+        destination: destination.pubkey(),
+        system_program: system_program::id(),
+        clock: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::UserReachBuyLimit.into());
+}
@@ -0,0 +1,959 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::prelude::Clock;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use nft_minter::utils::Creator;
+use nft_shop::error::ErrorCode;
+use nft_shop::pda::*;
+use nft_shop::state::{Market, MarketState, PriceTier, SellingResource, TradeHistory};
+use nft_shop::utils::EDITION_ACCOUNTS_LEN;
+use solana_program_test::tokio;
+use std::time::SystemTime;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn buy_many_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(2);
+    let quantity = 2;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price * quantity, // Selling Token price
+    )
+    .await;
+
+    let mut edition_accounts = Vec::new();
+    for edition in 1..=quantity {
+        edition_accounts.append(
+            &mut create_edition_accounts(
+                &mut context,
+                &user_wallet,
+                &selling_resource.resource,
+                edition,
+            )
+            .await,
+        );
+    }
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::BuyMany {
+        quantity,
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::BuyMany {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        master_edition,
+        vault: selling_resource.vault,
+        vault_owner,
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(selling_resource_data.supply, quantity);
+    assert_eq!(trade_history_data.already_bought, quantity);
+    assert_eq!(market_data.funds_collected, price * quantity);
+    assert_eq!(market_data.state, MarketState::Ended);
+}
+
+#[tokio::test]
+async fn buy_many_price_tiers_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(2);
+    let quantity = 2;
+    // The first edition is sold at early-bird price, the second one at `price`
+    let tier_price = ONE_SOL;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![PriceTier {
+            supply_threshold: 1,
+            price: tier_price,
+        }],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        tier_price + price, // Selling Token price
+    )
+    .await;
+
+    let mut edition_accounts = Vec::new();
+    for edition in 1..=quantity {
+        edition_accounts.append(
+            &mut create_edition_accounts(
+                &mut context,
+                &user_wallet,
+                &selling_resource.resource,
+                edition,
+            )
+            .await,
+        );
+    }
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::BuyMany {
+        quantity,
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::BuyMany {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        master_edition,
+        vault: selling_resource.vault,
+        vault_owner,
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let trade_history_account = context
+        .banks_client
+        .get_account(trade_history)
+        .await
+        .unwrap()
+        .unwrap();
+    let trade_history_data =
+        TradeHistory::try_deserialize(&mut trade_history_account.data.as_ref()).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(selling_resource_data.supply, quantity);
+    assert_eq!(trade_history_data.already_bought, quantity);
+    assert_eq!(market_data.funds_collected, tier_price + price);
+    assert_eq!(market_data.state, MarketState::Ended);
+}
+
+#[tokio::test]
+async fn failure_buy_many_user_reach_buy_limit() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+    let quantity = 2;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::UserReachBuyLimit.into());
+}
+
+#[tokio::test]
+async fn failure_buy_many_allocation_is_exhausted() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = None;
+    let quantity = 2;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(1),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::AllocationIsExhausted.into());
+}
+
+#[tokio::test]
+async fn failure_buy_many_edition_accounts_mismatch() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(2);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(2);
+    let quantity = 2;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    // Accounts of the last edition are missed
+    edition_accounts.truncate(edition_accounts.len() - EDITION_ACCOUNTS_LEN);
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.append(&mut edition_accounts);
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::EditionAccountsMismatch.into());
+}
@@ -0,0 +1,366 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::prelude::Clock;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::AccountDeserialize;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use nft_minter::utils::Creator;
+use nft_shop::error::ErrorCode;
+use nft_shop::events::MarketCancelled;
+use nft_shop::pda::*;
+use nft_shop::state::{Market, MarketState};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn cancel_market_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // CancelMarket
+    let accounts = nft_shop::accounts::CancelMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = nft_shop::instruction::CancelMarket {}.data();
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(market_data.state, MarketState::Cancelled);
+
+    let events = find_events::<MarketCancelled>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+}
+
+#[tokio::test]
+async fn failure_cancel_market_is_ended() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // CloseMarket
+    let accounts = nft_shop::accounts::CloseMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = nft_shop::instruction::CloseMarket {}.data();
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 3).unwrap();
+
+    // CancelMarket
+    let accounts = nft_shop::accounts::CancelMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = nft_shop::instruction::CancelMarket {}.data();
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketInInvalidState.into());
+}
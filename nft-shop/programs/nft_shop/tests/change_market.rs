@@ -5,10 +5,12 @@ use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::{system_program, sysvar};
 use anchor_lang::AccountDeserialize;
 use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::MarketChanged;
 use nft_shop::pda::*;
-use nft_shop::state::Market;
+use nft_shop::state::{Market, PriceTier};
 use nft_shop::utils::{puffed_out_string, DESCRIPTION_MAX_LEN, NAME_MAX_LEN};
 use solana_program_test::tokio;
 use std::time::SystemTime;
@@ -61,9 +63,10 @@ async fn change_market_success() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -90,7 +93,6 @@ async fn change_market_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -98,6 +100,12 @@ async fn change_market_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -136,12 +144,22 @@ async fn change_market_success() {
         mutable: None,
         new_price: None,
         new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        new_price_tiers: None,
     };
 
     let accounts = nft_shop::accounts::ChangeMarket {
         market: market_keypair.pubkey(),
         selling_resource_owner: selling_resource_owner_keypair.pubkey(),
         clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
     };
 
     let ix = Instruction {
@@ -156,7 +174,7 @@ async fn change_market_success() {
         &[&context.payer, &selling_resource_owner_keypair],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
     context.warp_to_slot(clock.slot + 3).unwrap();
@@ -178,10 +196,16 @@ async fn change_market_success() {
         puffed_out_string(String::from("2"), DESCRIPTION_MAX_LEN),
         market_data.description
     );
+
+    let events = find_events::<MarketChanged>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(events[0].price, market_data.price);
 }
 
 #[tokio::test]
-async fn failure_market_is_ended() {
+async fn change_market_dates_success() {
     let mut context = nft_shop_program_test().start_with_context().await;
 
     let (store_admin, store_keypair) = create_store(&mut context).await;
@@ -225,9 +249,10 @@ async fn failure_market_is_ended() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -239,15 +264,13 @@ async fn failure_market_is_ended() {
     )
     .await;
 
-    let start_date = (SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()) as u64;
-    let end_date = (SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()
-        + 60) as u64;
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
 
     let market_name = "1234_1234_".to_string();
     let market_description = "1234_1234_1234_1234_".to_string();
@@ -256,14 +279,19 @@ async fn failure_market_is_ended() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
         price,
         pieces_in_one_wallet,
-        start_date: start_date,
-        end_date: Some(end_date),
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -295,21 +323,213 @@ async fn failure_market_is_ended() {
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    context.warp_to_slot(120 * 400 * 2).unwrap();
+    // ChangeMarket
+    let new_start_date = start_date as u64 + 100;
+    let new_end_date = start_date as u64 + 200;
+
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: None,
+        new_description: None,
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: Some(new_start_date),
+        new_end_date: Some(Some(new_end_date)),
+        new_price_tiers: None,
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 3).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(market_data.start_date, new_start_date);
+    assert_eq!(market_data.end_date, Some(new_end_date));
+
+    let events = find_events::<MarketChanged>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].start_date, new_start_date);
+    assert_eq!(events[0].end_date, Some(new_end_date));
+}
+
+#[tokio::test]
+async fn change_market_clear_end_date_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+    let end_date = start_date as u64 + 200;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: Some(end_date),
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
 
     // ChangeMarket
     let data = nft_shop::instruction::ChangeMarket {
-        new_name: Some(String::from("1")),
-        new_description: Some(String::from("2")),
+        new_name: None,
+        new_description: None,
         mutable: None,
         new_price: None,
         new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: Some(None),
+        new_price_tiers: None,
     };
 
     let accounts = nft_shop::accounts::ChangeMarket {
         market: market_keypair.pubkey(),
         selling_resource_owner: selling_resource_owner_keypair.pubkey(),
         clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
     };
 
     let ix = Instruction {
@@ -324,18 +544,31 @@ async fn failure_market_is_ended() {
         &[&context.payer, &selling_resource_owner_keypair],
         context.last_blockhash,
     );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
-    let tx_error = context
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 3).unwrap();
+
+    let market_account = context
         .banks_client
-        .process_transaction(tx)
+        .get_account(market_keypair.pubkey())
         .await
-        .unwrap_err();
+        .expect("Account not found")
+        .expect("Account is empty");
 
-    assert_error(tx_error, ErrorCode::MarketIsEnded.into());
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(market_data.start_date, start_date as u64);
+    assert_eq!(market_data.end_date, None);
+
+    let events = find_events::<MarketChanged>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].end_date, None);
 }
 
 #[tokio::test]
-async fn failure_market_is_immutable() {
+async fn change_market_treasury_success() {
     let mut context = nft_shop_program_test().start_with_context().await;
 
     let (store_admin, store_keypair) = create_store(&mut context).await;
@@ -379,9 +612,10 @@ async fn failure_market_is_immutable() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -403,12 +637,11 @@ async fn failure_market_is_immutable() {
 
     let market_name = "1234_1234_".to_string();
     let market_description = "1234_1234_1234_1234_".to_string();
-    let mutable = false; // Immutable Market
+    let mutable = true;
     let price = ONE_SOL;
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -416,6 +649,12 @@ async fn failure_market_is_immutable() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -448,18 +687,52 @@ async fn failure_market_is_immutable() {
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // ChangeMarket
+    let new_treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &new_treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (new_treasury_owner, _) = find_treasury_owner_address(
+        &new_treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let new_treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_treasury_holder_keypair,
+        &new_treasury_mint_keypair.pubkey(),
+        &new_treasury_owner,
+    )
+    .await;
+
     let data = nft_shop::instruction::ChangeMarket {
-        new_name: Some(String::from("1")),
-        new_description: Some(String::from("2")),
+        new_name: None,
+        new_description: None,
         mutable: None,
         new_price: None,
         new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        new_price_tiers: None,
     };
 
     let accounts = nft_shop::accounts::ChangeMarket {
         market: market_keypair.pubkey(),
         selling_resource_owner: selling_resource_owner_keypair.pubkey(),
         clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: Some(new_treasury_mint_keypair.pubkey()),
+        treasury_holder: Some(new_treasury_holder_keypair.pubkey()),
+        treasury_owner: Some(new_treasury_owner),
+        old_treasury_holder: Some(treasury_holder_keypair.pubkey()),
+        old_treasury_owner: Some(treasury_owner),
+        old_treasury_token_program: Some(spl_token::id()),
     };
 
     let ix = Instruction {
@@ -474,12 +747,911 @@ async fn failure_market_is_immutable() {
         &[&context.payer, &selling_resource_owner_keypair],
         context.last_blockhash,
     );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
-    let tx_error = context
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 3).unwrap();
+
+    let market_account = context
         .banks_client
-        .process_transaction(tx)
+        .get_account(market_keypair.pubkey())
         .await
-        .unwrap_err();
+        .expect("Account not found")
+        .expect("Account is empty");
 
-    assert_error(tx_error, ErrorCode::MarketIsImmutable.into());
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(
+        market_data.treasury_mint,
+        new_treasury_mint_keypair.pubkey()
+    );
+    assert_eq!(
+        market_data.treasury_holder,
+        new_treasury_holder_keypair.pubkey()
+    );
+    assert_eq!(market_data.treasury_owner, new_treasury_owner);
+
+    // Replaced treasury is closed
+    let old_treasury_holder_account = context
+        .banks_client
+        .get_account(treasury_holder_keypair.pubkey())
+        .await
+        .unwrap();
+
+    assert!(old_treasury_holder_account.is_none());
+
+    let events = find_events::<MarketChanged>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].treasury_mint, new_treasury_mint_keypair.pubkey());
+}
+
+#[tokio::test]
+async fn failure_market_is_ended() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+    let end_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+        + 60) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date,
+        end_date: Some(end_date),
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    context.warp_to_slot(120 * 400 * 2).unwrap();
+
+    // ChangeMarket
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: Some(String::from("1")),
+        new_description: Some(String::from("2")),
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        new_price_tiers: None,
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsEnded.into());
+}
+
+#[tokio::test]
+async fn failure_market_is_immutable() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = false; // Immutable Market
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ChangeMarket
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: Some(String::from("1")),
+        new_description: Some(String::from("2")),
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        new_price_tiers: None,
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsImmutable.into());
+}
+
+#[tokio::test]
+async fn failure_change_market_is_started() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // ChangeMarket
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: None,
+        new_description: None,
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: Some(start_date as u64 + 100),
+        new_end_date: None,
+        new_price_tiers: None,
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::MarketIsStarted.into());
+}
+
+#[tokio::test]
+async fn change_market_price_tiers_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ChangeMarket
+    let price_tiers = vec![
+        PriceTier {
+            supply_threshold: 100,
+            price: ONE_SOL / 2,
+        },
+        PriceTier {
+            supply_threshold: 500,
+            price: ONE_SOL,
+        },
+    ];
+
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: None,
+        new_description: None,
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        new_price_tiers: Some(price_tiers.clone()),
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 3).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(market_data.price_tiers, price_tiers);
+
+    let events = find_events::<MarketChanged>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(events[0].price_tiers, price_tiers);
+}
+
+#[tokio::test]
+async fn failure_change_market_invalid_price_tiers() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ChangeMarket
+    let data = nft_shop::instruction::ChangeMarket {
+        new_name: None,
+        new_description: None,
+        mutable: None,
+        new_price: None,
+        new_pieces_in_one_wallet: None,
+        new_start_date: None,
+        new_end_date: None,
+        // Supply thresholds should go up
+        new_price_tiers: Some(vec![
+            PriceTier {
+                supply_threshold: 500,
+                price: ONE_SOL / 2,
+            },
+            PriceTier {
+                supply_threshold: 100,
+                price: ONE_SOL,
+            },
+        ]),
+    };
+
+    let accounts = nft_shop::accounts::ChangeMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+        system_program: system_program::id(),
+        treasury_mint: None,
+        treasury_holder: None,
+        treasury_owner: None,
+        old_treasury_holder: None,
+        old_treasury_owner: None,
+        old_treasury_token_program: None,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::InvalidPriceTiers.into());
 }
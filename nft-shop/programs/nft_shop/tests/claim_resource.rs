@@ -2,6 +2,7 @@ use anchor_client::solana_sdk::transaction::Transaction;
 use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
 use anchor_lang::prelude::{AccountMeta, Clock};
 use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
 use anchor_lang::solana_program::{system_program, sysvar};
 use anchor_lang::{prelude::Pubkey, AccountDeserialize};
 use anchor_lang::{InstructionData, ToAccountMetas};
@@ -9,8 +10,10 @@ use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::spl_token;
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::ResourceClaimed;
 use nft_shop::pda::*;
-use nft_shop::state::SellingResource;
+use nft_shop::state::{Market, SellingResource, SellingResourceState, Store};
+use nft_shop::utils::REFUND_PERIOD;
 use solana_program_test::tokio;
 use std::time::SystemTime;
 
@@ -64,9 +67,10 @@ async fn claim_resource_success() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -90,7 +94,6 @@ async fn claim_resource_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -98,6 +101,12 @@ async fn claim_resource_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -145,9 +154,9 @@ async fn claim_resource_success() {
     let selling_resource =
         SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
 
-    let (trade_history, trade_history_bump) =
+    let (trade_history, _) =
         find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
-    let (vault_owner, vault_owner_bump) =
+    let (vault_owner, _) =
         find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
 
     let user_wallet = context.payer.dirty_clone();
@@ -242,11 +251,9 @@ async fn claim_resource_success() {
 
     let primary_royalties_holder = Keypair::new();
 
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
 
     let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
         creators: vec![nft_shop::state::Creator {
             address: primary_royalties_holder.pubkey(),
             verified: false,
@@ -278,8 +285,7 @@ async fn claim_resource_success() {
     // Buy
 
     let data = nft_shop::instruction::Buy {
-        _trade_history_bump: trade_history_bump,
-        vault_owner_bump,
+        allowlist_proof: None,
     };
 
     let accounts = nft_shop::accounts::Buy {
@@ -303,6 +309,13 @@ async fn claim_resource_success() {
         token_metadata_program: mpl_token_metadata::id(),
         token_program: spl_token::id(),
         system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators,
     };
 
     let ix = Instruction {
@@ -349,7 +362,7 @@ async fn claim_resource_success() {
 
     // Withdraw
 
-    let (payout_ticket, payout_ticket_bump) =
+    let (payout_ticket, _) =
         find_payout_ticket_address(&market_keypair.pubkey(), &primary_royalties_holder.pubkey());
 
     let destination = get_associated_token_address(
@@ -366,10 +379,7 @@ async fn claim_resource_success() {
         &mpl_token_metadata::id(),
     );
 
-    let data = nft_shop::instruction::Withdraw {
-        payout_ticket_bump,
-        treasury_owner_bump: treasyry_owner_bump,
-    };
+    let data = nft_shop::instruction::Withdraw {};
 
     let accounts = nft_shop::accounts::Withdraw {
         market: market_keypair.pubkey(),
@@ -417,10 +427,11 @@ async fn claim_resource_success() {
     )
     .await;
 
-    let data = nft_shop::instruction::ClaimResource { vault_owner_bump };
+    let data = nft_shop::instruction::ClaimResource {};
 
     let accounts = nft_shop::accounts::ClaimResource {
         market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
         treasury_holder: treasury_holder_keypair.pubkey(),
         selling_resource: selling_resource_keypair.pubkey(),
         selling_resource_owner: selling_resource_owner_keypair.pubkey(),
@@ -434,10 +445,13 @@ async fn claim_resource_success() {
         system_program: system_program::id(),
     };
 
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.push(AccountMeta::new_readonly(primary_metadata_creators, false));
+
     let ix = Instruction {
         program_id: nft_shop::id(),
         data: data.data(),
-        accounts: accounts.to_account_metas(None),
+        accounts: account_metas,
     };
 
     let tx = Transaction::new_signed_with_payer(
@@ -446,7 +460,38 @@ async fn claim_resource_success() {
         &[&context.payer, &selling_resource_owner_keypair],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let selling_resource_acc = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_acc.data.as_ref()).unwrap();
+
+    assert_eq!(selling_resource_data.state, SellingResourceState::Stopped);
+
+    let store_acc = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let store_data = Store::try_deserialize(&mut store_acc.data.as_ref()).unwrap();
+
+    assert_eq!(store_data.active_selling_resources, 0);
+
+    let events = find_events::<ResourceClaimed>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(
+        events[0].selling_resource,
+        selling_resource_keypair.pubkey()
+    );
+    assert_eq!(events[0].destination, destination_token_account.pubkey());
 }
 
 #[tokio::test]
@@ -496,9 +541,10 @@ async fn failure_claim_resource_invalid_treasury_amount() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -522,7 +568,6 @@ async fn failure_claim_resource_invalid_treasury_amount() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -530,6 +575,12 @@ async fn failure_claim_resource_invalid_treasury_amount() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -577,9 +628,9 @@ async fn failure_claim_resource_invalid_treasury_amount() {
     let selling_resource =
         SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
 
-    let (trade_history, trade_history_bump) =
+    let (trade_history, _) =
         find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
-    let (vault_owner, vault_owner_bump) =
+    let (vault_owner, _) =
         find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
 
     let user_wallet = context.payer.dirty_clone();
@@ -674,11 +725,9 @@ async fn failure_claim_resource_invalid_treasury_amount() {
 
     let primary_royalties_holder = Keypair::new();
 
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&metadata);
 
     let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
         creators: vec![nft_shop::state::Creator {
             address: primary_royalties_holder.pubkey(),
             verified: false,
@@ -710,8 +759,7 @@ async fn failure_claim_resource_invalid_treasury_amount() {
     // Buy
 
     let data = nft_shop::instruction::Buy {
-        _trade_history_bump: trade_history_bump,
-        vault_owner_bump,
+        allowlist_proof: None,
     };
 
     let accounts = nft_shop::accounts::Buy {
@@ -735,6 +783,13 @@ async fn failure_claim_resource_invalid_treasury_amount() {
         token_metadata_program: mpl_token_metadata::id(),
         token_program: spl_token::id(),
         system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators,
     };
 
     let ix = Instruction {
@@ -790,10 +845,11 @@ async fn failure_claim_resource_invalid_treasury_amount() {
     )
     .await;
 
-    let data = nft_shop::instruction::ClaimResource { vault_owner_bump };
+    let data = nft_shop::instruction::ClaimResource {};
 
     let accounts = nft_shop::accounts::ClaimResource {
         market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
         treasury_holder: treasury_holder_keypair.pubkey(),
         selling_resource: selling_resource_keypair.pubkey(),
         selling_resource_owner: selling_resource_owner_keypair.pubkey(),
@@ -807,10 +863,13 @@ async fn failure_claim_resource_invalid_treasury_amount() {
         system_program: system_program::id(),
     };
 
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.push(AccountMeta::new_readonly(primary_metadata_creators, false));
+
     let ix = Instruction {
         program_id: nft_shop::id(),
         data: data.data(),
-        accounts: accounts.to_account_metas(None),
+        accounts: account_metas,
     };
 
     let tx = Transaction::new_signed_with_payer(
@@ -828,3 +887,424 @@ async fn failure_claim_resource_invalid_treasury_amount() {
 
     assert_error(tx_error, ErrorCode::TreasuryIsNotEmpty.into());
 }
+
+#[tokio::test]
+async fn claim_resource_cancelled_market_after_refund_period() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // CreateMarket
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = (SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()) as u64;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = 2 * ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Waiting for Market`s start
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    // Buy
+    let selling_resource_data = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let selling_resource =
+        SellingResource::try_deserialize(&mut selling_resource_data.as_ref()).unwrap();
+
+    let (trade_history, _) =
+        find_trade_history_address(&context.payer.pubkey(), &market_keypair.pubkey());
+    let (vault_owner, _) =
+        find_vault_owner_address(&selling_resource.resource, &selling_resource.store);
+
+    let user_wallet = context.payer.dirty_clone();
+
+    let user_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &user_token_account,
+        &treasury_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &treasury_mint_keypair.pubkey(),
+        &user_token_account.pubkey(),
+        &store_admin,
+        price, // Selling Token price
+    )
+    .await;
+
+    let new_mint_keypair = Keypair::new();
+    create_mint(&mut context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_mint_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_mint_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &new_mint_keypair.pubkey(),
+        &new_mint_token_account.pubkey(),
+        &user_wallet,
+        1,
+    )
+    .await;
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            selling_resource.resource.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            selling_resource.supply.to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            new_mint_keypair.pubkey().as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    let data = nft_shop::instruction::Buy {
+        allowlist_proof: None,
+    };
+
+    let accounts = nft_shop::accounts::Buy {
+        market: market_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        user_token_account: user_token_account.pubkey(),
+        user_wallet: user_wallet.pubkey(),
+        trade_history,
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint: new_mint_keypair.pubkey(),
+        edition_marker,
+        vault: selling_resource.vault,
+        vault_owner,
+        new_token_account: new_mint_token_account.pubkey(),
+        metadata,
+        clock: sysvar::clock::id(),
+        rent: sysvar::rent::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        referral: None,
+        treasury_mint: None,
+        treasury_token_program: None,
+        gate_token_account: None,
+        gate_metadata: None,
+        gate_usage: None,
+        primary_metadata_creators: find_primary_metadata_creators(&metadata).0,
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // CancelMarket
+
+    let accounts = nft_shop::accounts::CancelMarket {
+        market: market_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        clock: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = nft_shop::instruction::CancelMarket {}.data();
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ClaimResource before the refund period is over
+
+    let destination_token_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &destination_token_account,
+        &selling_resource.resource,
+        &selling_resource_owner_keypair.pubkey(),
+    )
+    .await;
+
+    let data = nft_shop::instruction::ClaimResource {};
+
+    let accounts = nft_shop::accounts::ClaimResource {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        vault: selling_resource.vault,
+        metadata,
+        vault_owner,
+        destination: destination_token_account.pubkey(),
+        clock: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        token_metadata_program: mpl_token_metadata::id(),
+        system_program: system_program::id(),
+    };
+
+    let claim_ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    // Buyer wasn't refunded yet
+    assert_error(tx_error, ErrorCode::FundsAreNotRefunded.into());
+
+    // ClaimResource after the refund period
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += REFUND_PERIOD as i64 + 1;
+    context.set_sysvar(&clock);
+
+    let last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &selling_resource_owner_keypair],
+        last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let destination_token_account_data = context
+        .banks_client
+        .get_account(destination_token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let destination_token_account_data =
+        spl_token::state::Account::unpack(&destination_token_account_data).unwrap();
+
+    assert_eq!(destination_token_account_data.amount, 1);
+
+    // Refund is still available after the resource is claimed
+
+    let data = nft_shop::instruction::Refund {};
+
+    let accounts = nft_shop::accounts::Refund {
+        market: market_keypair.pubkey(),
+        trade_history,
+        user_wallet: user_wallet.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_owner,
+        destination: user_token_account.pubkey(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&user_wallet.pubkey()),
+        &[&user_wallet],
+        last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let market_acc = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let market_data = Market::try_deserialize(&mut market_acc.data.as_ref()).unwrap();
+
+    assert!(market_data.claimed);
+    assert_eq!(market_data.funds_refunded, price);
+}
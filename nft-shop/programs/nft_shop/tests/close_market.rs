@@ -7,6 +7,7 @@ use anchor_lang::AccountDeserialize;
 use anchor_lang::{InstructionData, ToAccountMetas};
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::MarketClosed;
 use nft_shop::pda::*;
 use nft_shop::state::{Market, MarketState};
 use solana_program_test::tokio;
@@ -61,9 +62,10 @@ async fn close_market_success() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -90,7 +92,6 @@ async fn close_market_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -98,6 +99,12 @@ async fn close_market_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -155,7 +162,7 @@ async fn close_market_success() {
         &[&context.payer, &selling_resource_owner_keypair],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let market_account = context
         .banks_client
@@ -167,6 +174,11 @@ async fn close_market_success() {
     let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
 
     assert_eq!(market_data.state, MarketState::Ended);
+
+    let events = find_events::<MarketClosed>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
 }
 
 #[tokio::test]
@@ -216,9 +228,10 @@ async fn failure_close_market_with_limited_duration() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -246,7 +259,6 @@ async fn failure_close_market_with_limited_duration() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -254,6 +266,12 @@ async fn failure_close_market_with_limited_duration() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: Some(end_date as u64),
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -0,0 +1,105 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use nft_minter::utils::Creator;
+use nft_shop::error::ErrorCode;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn close_store_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let data = nft_shop::instruction::CloseStore {};
+
+    let accounts = nft_shop::accounts::CloseStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let store_account = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .unwrap();
+
+    assert!(store_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_close_store_has_active_selling_resources() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        Some(vec![Creator {
+            address: store_admin.pubkey(),
+            verified: false,
+            share: 100,
+        }]),
+        Some(1),
+        true,
+    )
+    .await;
+
+    let data = nft_shop::instruction::CloseStore {};
+
+    let accounts = nft_shop::accounts::CloseStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::StoreHasActiveSellingResources.into());
+}
@@ -7,8 +7,9 @@ use anchor_lang::AccountDeserialize;
 use anchor_lang::{InstructionData, ToAccountMetas};
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::MarketCreated;
 use nft_shop::pda::*;
-use nft_shop::state::{Market, MarketState};
+use nft_shop::state::{Market, MarketState, PriceTier, PricingCurve, SellingResource};
 use nft_shop::utils::{puffed_out_string, DESCRIPTION_MAX_LEN, NAME_MAX_LEN};
 use solana_program_test::tokio;
 
@@ -63,6 +64,7 @@ async fn create_market_success() {
     let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -89,7 +91,6 @@ async fn create_market_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -97,6 +98,12 @@ async fn create_market_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -126,7 +133,7 @@ async fn create_market_success() {
         ],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let market_account = context
         .banks_client
@@ -148,6 +155,7 @@ async fn create_market_success() {
         market_data.treasury_holder
     );
     assert_eq!(treasury_owner, market_data.treasury_owner);
+    assert_eq!(treasyry_owner_bump, market_data.treasury_owner_bump);
     assert_eq!(selling_resource_owner_keypair.pubkey(), market_data.owner);
     assert_eq!(
         puffed_out_string(market_name, NAME_MAX_LEN),
@@ -157,10 +165,803 @@ async fn create_market_success() {
         puffed_out_string(market_description, DESCRIPTION_MAX_LEN),
         market_data.description
     );
-    assert_eq!(mutable, market_data.mutable);
-    assert_eq!(price, market_data.price);
-    assert_eq!(pieces_in_one_wallet, market_data.pieces_in_one_wallet);
-    assert_eq!(MarketState::Created, market_data.state);
+    assert_eq!(mutable, market_data.mutable);
+    assert_eq!(price, market_data.price);
+    assert_eq!(pieces_in_one_wallet, market_data.pieces_in_one_wallet);
+    assert_eq!(MarketState::Created, market_data.state);
+
+    let events = find_events::<MarketCreated>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].market, market_keypair.pubkey());
+    assert_eq!(
+        events[0].selling_resource,
+        selling_resource_keypair.pubkey()
+    );
+    assert_eq!(events[0].treasury_mint, treasury_mint_keypair.pubkey());
+    assert_eq!(events[0].price, price);
+}
+
+#[tokio::test]
+async fn create_native_market_with_prefunded_treasury_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &system_program::id(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    // Anyone can send lamports to the treasury owner before `Market` is created
+    airdrop(&mut context, &treasury_owner, ONE_SOL)
+        .await
+        .unwrap();
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: "1234_1234_".to_string(),
+        description: "1234_1234_1234_1234_".to_string(),
+        mutable: true,
+        price: ONE_SOL,
+        pieces_in_one_wallet: Some(1),
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: system_program::id(),
+        treasury_holder: treasury_owner,
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    let treasury_owner_account = context
+        .banks_client
+        .get_account(treasury_owner)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(system_program::id(), market_data.treasury_mint);
+    assert_eq!(treasury_owner, market_data.treasury_holder);
+    assert_eq!(treasury_owner, market_data.treasury_owner);
+    // Treasury owner is already rent exempt, so it isn't funded again
+    assert_eq!(treasury_owner_account.lamports, ONE_SOL);
+}
+
+#[tokio::test]
+async fn create_market_with_allocations_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(3);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // Sell one edition in the first Market
+    let market_keypair_1 = Keypair::new();
+
+    let treasury_mint_keypair_1 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_1,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_1, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_1.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_1.pubkey(),
+    );
+
+    let treasury_holder_keypair_1 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_1,
+        &treasury_mint_keypair_1.pubkey(),
+        &treasury_owner_1,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(1),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_1.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_1.pubkey(),
+        treasury_holder: treasury_holder_keypair_1.pubkey(),
+        treasury_owner: treasury_owner_1,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_1,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // And the rest in the second one
+    let market_keypair_2 = Keypair::new();
+
+    let treasury_mint_keypair_2 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_2,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_2, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_2.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_2.pubkey(),
+    );
+
+    let treasury_holder_keypair_2 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_2,
+        &treasury_mint_keypair_2.pubkey(),
+        &treasury_owner_2,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(2),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_2.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_2.pubkey(),
+        treasury_holder: treasury_holder_keypair_2.pubkey(),
+        treasury_owner: treasury_owner_2,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_2,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let market_account = context
+        .banks_client
+        .get_account(market_keypair_2.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let market_data = Market::try_deserialize(&mut market_account.data.as_ref()).unwrap();
+
+    assert_eq!(selling_resource_data.allocated, 3);
+    assert_eq!(selling_resource_data.markets, 2);
+    assert_eq!(market_data.allocation, Some(2));
+    assert_eq!(treasury_mint_keypair_2.pubkey(), market_data.treasury_mint);
+
+    let events = find_events::<MarketCreated>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].allocation, Some(2));
+}
+
+#[tokio::test]
+async fn failure_market_allocation_is_too_big() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(3);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair_1 = Keypair::new();
+
+    let treasury_mint_keypair_1 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_1,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_1, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_1.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_1.pubkey(),
+    );
+
+    let treasury_holder_keypair_1 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_1,
+        &treasury_mint_keypair_1.pubkey(),
+        &treasury_owner_1,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(1),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_1.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_1.pubkey(),
+        treasury_holder: treasury_holder_keypair_1.pubkey(),
+        treasury_owner: treasury_owner_1,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_1,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let market_keypair_2 = Keypair::new();
+
+    let treasury_mint_keypair_2 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_2,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_2, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_2.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_2.pubkey(),
+    );
+
+    let treasury_holder_keypair_2 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_2,
+        &treasury_mint_keypair_2.pubkey(),
+        &treasury_owner_2,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(3),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_2.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_2.pubkey(),
+        treasury_holder: treasury_holder_keypair_2.pubkey(),
+        treasury_owner: treasury_owner_2,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_2,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::AllocationIsTooBig.into());
+}
+
+#[tokio::test]
+async fn failure_market_allocation_after_whole_resource_market() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(3);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    // First Market takes the whole resource
+    let market_keypair_1 = Keypair::new();
+
+    let treasury_mint_keypair_1 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_1,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_1, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_1.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_1.pubkey(),
+    );
+
+    let treasury_holder_keypair_1 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_1,
+        &treasury_mint_keypair_1.pubkey(),
+        &treasury_owner_1,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_1.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_1.pubkey(),
+        treasury_holder: treasury_holder_keypair_1.pubkey(),
+        treasury_owner: treasury_owner_1,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_1,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let market_keypair_2 = Keypair::new();
+
+    let treasury_mint_keypair_2 = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair_2,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner_2, _) = find_treasury_owner_address(
+        &treasury_mint_keypair_2.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair_2.pubkey(),
+    );
+
+    let treasury_holder_keypair_2 = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair_2,
+        &treasury_mint_keypair_2.pubkey(),
+        &treasury_owner_2,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: Some(2),
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair_2.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair_2.pubkey(),
+        treasury_holder: treasury_holder_keypair_2.pubkey(),
+        treasury_owner: treasury_owner_2,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair_2,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::SellingResourceAlreadyTaken.into());
 }
 
 #[tokio::test]
@@ -208,9 +1009,10 @@ async fn failure_market_name_is_long() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -237,7 +1039,6 @@ async fn failure_market_name_is_long() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -245,6 +1046,12 @@ async fn failure_market_name_is_long() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -329,9 +1136,10 @@ async fn failure_description_name_is_long() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -359,7 +1167,6 @@ async fn failure_description_name_is_long() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -367,6 +1174,12 @@ async fn failure_description_name_is_long() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -452,9 +1265,10 @@ async fn failure_market_signer_is_missed() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -481,7 +1295,6 @@ async fn failure_market_signer_is_missed() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -489,6 +1302,12 @@ async fn failure_market_signer_is_missed() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -566,9 +1385,10 @@ async fn failure_market_start_date_is_in_the_past() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -595,7 +1415,6 @@ async fn failure_market_start_date_is_in_the_past() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -603,6 +1422,12 @@ async fn failure_market_start_date_is_in_the_past() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -687,9 +1512,10 @@ async fn failure_market_end_date_is_earlier_than_start_date() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -718,7 +1544,6 @@ async fn failure_market_end_date_is_earlier_than_start_date() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -726,6 +1551,12 @@ async fn failure_market_end_date_is_earlier_than_start_date() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: Some(end_date as u64),
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -804,9 +1635,10 @@ async fn failure_treasury_mint_uninitialized() {
 
     let treasury_mint_keypair = Keypair::new();
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -833,7 +1665,6 @@ async fn failure_treasury_mint_uninitialized() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -841,6 +1672,12 @@ async fn failure_treasury_mint_uninitialized() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -920,9 +1757,10 @@ async fn failure_treasury_holder_uninitialized() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -942,7 +1780,6 @@ async fn failure_treasury_holder_uninitialized() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -950,6 +1787,12 @@ async fn failure_treasury_holder_uninitialized() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -982,3 +1825,273 @@ async fn failure_treasury_holder_uninitialized() {
 
     context.banks_client.process_transaction(tx).await.unwrap();
 }
+
+#[tokio::test]
+async fn failure_market_pricing_curve_floor_is_gt_than_price() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+    let pricing_curve = PricingCurve {
+        floor_price: 2 * ONE_SOL,
+        decay_interval: 60,
+        decay_step: ONE_SOL / 4,
+    };
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: Some(pricing_curve),
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::InvalidPricingCurve.into());
+}
+
+#[tokio::test]
+async fn failure_market_price_tiers_are_unsorted() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, _) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+    // Supply thresholds should grow strictly
+    let price_tiers = vec![
+        PriceTier {
+            supply_threshold: 2,
+            price: 2 * ONE_SOL,
+        },
+        PriceTier {
+            supply_threshold: 1,
+            price: 3 * ONE_SOL,
+        },
+    ];
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers,
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::InvalidPriceTiers.into());
+}
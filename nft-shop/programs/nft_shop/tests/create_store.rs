@@ -5,6 +5,7 @@ use anchor_lang::solana_program::system_program;
 use anchor_lang::AccountDeserialize;
 use anchor_lang::{InstructionData, ToAccountMetas};
 use nft_shop::error::ErrorCode;
+use nft_shop::events::StoreCreated;
 use nft_shop::state::Store;
 use nft_shop::utils::{puffed_out_string, DESCRIPTION_MAX_LEN, NAME_MAX_LEN};
 use solana_program_test::tokio;
@@ -49,7 +50,7 @@ async fn create_store_success() {
         &[&context.payer, &store_admin, &store],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let store_account = context
         .banks_client
@@ -69,6 +70,12 @@ async fn create_store_success() {
         puffed_out_string(description, DESCRIPTION_MAX_LEN),
         store_account_data.description
     );
+
+    let events = find_events::<StoreCreated>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].store, store.pubkey());
+    assert_eq!(events[0].admin, store_admin.pubkey());
 }
 
 #[tokio::test]
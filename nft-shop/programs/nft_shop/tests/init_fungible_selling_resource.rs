@@ -0,0 +1,256 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::AccountDeserialize;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use nft_minter::utils::Creator;
+use nft_shop::error::ErrorCode;
+use nft_shop::events::SellingResourceInitialized;
+use nft_shop::pda::*;
+use nft_shop::state::{SellingResource, SellingResourceKind, SellingResourceState};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn init_fungible_selling_resource_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let metadata_creators = Some(vec![Creator {
+        address: store_admin.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let amount = 10;
+
+    // Create Token to be provided as a resource for sale
+    let token = create_fungible_token(
+        &mut context,
+        store_admin.dirty_clone(),
+        metadata_creators,
+        amount,
+    )
+    .await
+    .unwrap();
+
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(&mut context, &vault, &token.mint.pubkey(), &vault_owner).await;
+
+    let selling_resource = Keypair::new();
+
+    let data = nft_shop::instruction::InitFungibleSellingResource { amount };
+
+    let accounts = nft_shop::accounts::InitFungibleSellingResource {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        selling_resource: selling_resource.pubkey(),
+        selling_resource_owner: store_admin.pubkey(),
+        resource_mint: token.mint.pubkey(),
+        resource_token: token.token,
+        metadata: token.metadata,
+        vault: vault.pubkey(),
+        vault_owner,
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&store_admin.pubkey()),
+        &[&store_admin, &selling_resource],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx).await;
+
+    let selling_resource_account = context
+        .banks_client
+        .get_account(selling_resource.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let selling_resource_data =
+        SellingResource::try_deserialize(&mut selling_resource_account.data.as_ref()).unwrap();
+
+    let vault_account = context
+        .banks_client
+        .get_account(vault.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let vault_data = spl_token::state::Account::unpack_from_slice(&vault_account.data).unwrap();
+
+    assert_eq!(store_keypair.pubkey(), selling_resource_data.store);
+    assert_eq!(token.mint.pubkey(), selling_resource_data.resource);
+    assert_eq!(vault.pubkey(), selling_resource_data.vault);
+    assert_eq!(0, selling_resource_data.supply);
+    assert_eq!(Some(amount), selling_resource_data.max_supply);
+    assert_eq!(SellingResourceState::Created, selling_resource_data.state);
+    assert_eq!(SellingResourceKind::Fungible, selling_resource_data.kind);
+    assert_eq!(amount, vault_data.amount);
+
+    let events = find_events::<SellingResourceInitialized>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].selling_resource, selling_resource.pubkey());
+    assert_eq!(events[0].max_supply, Some(amount));
+}
+
+#[tokio::test]
+async fn failure_resource_mint_has_decimals() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let resource_mint = Keypair::new();
+    create_mint(&mut context, &resource_mint, &store_admin.pubkey(), 6).await;
+
+    let resource_token = Keypair::new();
+    create_token_account(
+        &mut context,
+        &resource_token,
+        &resource_mint.pubkey(),
+        &store_admin.pubkey(),
+    )
+    .await;
+    mint_to(
+        &mut context,
+        &resource_mint.pubkey(),
+        &resource_token.pubkey(),
+        &store_admin,
+        10,
+    )
+    .await;
+
+    let (vault_owner, _) =
+        find_vault_owner_address(&resource_mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(&mut context, &vault, &resource_mint.pubkey(), &vault_owner).await;
+
+    // Mint with decimals has no metadata, so any existing one is provided
+    let other_token = create_fungible_token(&mut context, store_admin.dirty_clone(), None, 1)
+        .await
+        .unwrap();
+
+    let selling_resource = Keypair::new();
+
+    let data = nft_shop::instruction::InitFungibleSellingResource { amount: 10 };
+
+    let accounts = nft_shop::accounts::InitFungibleSellingResource {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        selling_resource: selling_resource.pubkey(),
+        selling_resource_owner: store_admin.pubkey(),
+        resource_mint: resource_mint.pubkey(),
+        resource_token: resource_token.pubkey(),
+        metadata: other_token.metadata,
+        vault: vault.pubkey(),
+        vault_owner,
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&store_admin.pubkey()),
+        &[&store_admin, &selling_resource],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::ResourceMintHasDecimals.into());
+}
+
+#[tokio::test]
+async fn failure_amount_is_zero() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let metadata_creators = Some(vec![Creator {
+        address: store_admin.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+
+    // Create Token to be provided as a resource for sale
+    let token = create_fungible_token(
+        &mut context,
+        store_admin.dirty_clone(),
+        metadata_creators,
+        10,
+    )
+    .await
+    .unwrap();
+
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(&mut context, &vault, &token.mint.pubkey(), &vault_owner).await;
+
+    let selling_resource = Keypair::new();
+
+    let data = nft_shop::instruction::InitFungibleSellingResource { amount: 0 };
+
+    let accounts = nft_shop::accounts::InitFungibleSellingResource {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        selling_resource: selling_resource.pubkey(),
+        selling_resource_owner: store_admin.pubkey(),
+        resource_mint: token.mint.pubkey(),
+        resource_token: token.token,
+        metadata: token.metadata,
+        vault: vault.pubkey(),
+        vault_owner,
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&store_admin.pubkey()),
+        &[&store_admin, &selling_resource],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::QuantityIsZero.into());
+}
@@ -7,6 +7,7 @@ use anchor_lang::{InstructionData, ToAccountMetas};
 use anchor_spl::token::spl_token;
 use nft_minter::utils::Creator;
 use nft_shop::error::ErrorCode;
+use nft_shop::events::SellingResourceInitialized;
 use nft_shop::pda::*;
 use nft_shop::state::{SellingResource, SellingResourceState};
 use solana_program_test::tokio;
@@ -50,11 +51,7 @@ async fn init_selling_resource_success() {
 
     let selling_resource = Keypair::new();
 
-    let data = nft_shop::instruction::InitSellingResource {
-        master_edition_bump: token.master_edition.bump,
-        vault_owner_bump,
-        max_supply,
-    };
+    let data = nft_shop::instruction::InitSellingResource { max_supply };
 
     let accounts = nft_shop::accounts::InitSellingResource {
         store: store_keypair.pubkey(),
@@ -84,7 +81,7 @@ async fn init_selling_resource_success() {
         &[&store_admin, &selling_resource],
         context.last_blockhash,
     );
-    context.banks_client.process_transaction(tx).await.unwrap();
+    let logs = process_transaction_with_logs(&mut context, tx).await;
 
     let selling_resource_account = context
         .banks_client
@@ -101,9 +98,18 @@ async fn init_selling_resource_success() {
     assert_eq!(token.mint.pubkey(), selling_resource_data.resource);
     assert_eq!(vault.pubkey(), selling_resource_data.vault);
     assert_eq!(vault_owner, selling_resource_data.vault_owner);
+    assert_eq!(vault_owner_bump, selling_resource_data.vault_owner_bump);
     assert_eq!(0, selling_resource_data.supply);
     assert_eq!(max_supply, selling_resource_data.max_supply);
     assert_eq!(SellingResourceState::Created, selling_resource_data.state);
+
+    let events = find_events::<SellingResourceInitialized>(&logs);
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].store, store_keypair.pubkey());
+    assert_eq!(events[0].selling_resource, selling_resource.pubkey());
+    assert_eq!(events[0].resource, token.mint.pubkey());
+    assert_eq!(events[0].max_supply, max_supply);
 }
 
 #[tokio::test]
@@ -131,8 +137,7 @@ async fn failure_supply_is_gt_than_available() {
     .await
     .unwrap();
 
-    let (vault_owner, vault_owner_bump) =
-        find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
 
     let vault = Keypair::new();
     create_token_account(&mut context, &vault, &token.mint.pubkey(), &vault_owner).await;
@@ -140,8 +145,6 @@ async fn failure_supply_is_gt_than_available() {
     let selling_resource = Keypair::new();
 
     let data = nft_shop::instruction::InitSellingResource {
-        master_edition_bump: token.master_edition.bump,
-        vault_owner_bump,
         max_supply: Some(1000), // Real max_suply = 1
     };
 
@@ -208,8 +211,7 @@ async fn failure_supply_is_not_provided() {
     .await
     .unwrap();
 
-    let (vault_owner, vault_owner_bump) =
-        find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
 
     let vault = Keypair::new();
     create_token_account(&mut context, &vault, &token.mint.pubkey(), &vault_owner).await;
@@ -217,8 +219,6 @@ async fn failure_supply_is_not_provided() {
     let selling_resource = Keypair::new();
 
     let data = nft_shop::instruction::InitSellingResource {
-        master_edition_bump: token.master_edition.bump,
-        vault_owner_bump,
         max_supply: None, // Real max_suply = 1
     };
 
@@ -59,9 +59,10 @@ async fn save_primary_metadata_creators_success() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -88,7 +89,6 @@ async fn save_primary_metadata_creators_success() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -96,6 +96,12 @@ async fn save_primary_metadata_creators_success() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -128,11 +134,9 @@ async fn save_primary_metadata_creators_success() {
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // SavePrimaryMetadataCreators
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&token.metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&token.metadata);
 
     let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
         creators: vec![nft_shop::state::Creator {
             address: store_admin.pubkey(),
             share: 100,
@@ -222,9 +226,10 @@ async fn failure_metadata_creators_is_gt_than_available() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -251,7 +256,6 @@ async fn failure_metadata_creators_is_gt_than_available() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -259,6 +263,12 @@ async fn failure_metadata_creators_is_gt_than_available() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -291,11 +301,9 @@ async fn failure_metadata_creators_is_gt_than_available() {
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // SavePrimaryMetadataCreators
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&token.metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&token.metadata);
 
     let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
         creators: vec![
             nft_shop::state::Creator {
                 address: store_admin.pubkey(),
@@ -404,9 +412,10 @@ async fn failure_metadata_creators_is_empty() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -433,7 +442,6 @@ async fn failure_metadata_creators_is_empty() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -441,6 +449,12 @@ async fn failure_metadata_creators_is_empty() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -473,13 +487,9 @@ async fn failure_metadata_creators_is_empty() {
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // SavePrimaryMetadataCreators
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&token.metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&token.metadata);
 
-    let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
-        creators: vec![],
-    };
+    let data = nft_shop::instruction::SavePrimaryMetadataCreators { creators: vec![] };
 
     let accounts = nft_shop::accounts::SavePrimaryMetadataCreators {
         metadata_update_authority: store_admin.pubkey(),
@@ -555,9 +565,10 @@ async fn failure_metadata_is_immutable() {
     )
     .await;
 
-    let (treasury_owner, treasyry_owner_bump) = find_treasury_owner_address(
+    let (treasury_owner, _) = find_treasury_owner_address(
         &treasury_mint_keypair.pubkey(),
         &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
     );
 
     let treasury_holder_keypair = Keypair::new();
@@ -584,7 +595,6 @@ async fn failure_metadata_is_immutable() {
     let pieces_in_one_wallet = Some(1);
 
     let data = nft_shop::instruction::CreateMarket {
-        _treasury_owner_bump: treasyry_owner_bump,
         name: market_name.to_owned(),
         description: market_description.to_owned(),
         mutable,
@@ -592,6 +602,12 @@ async fn failure_metadata_is_immutable() {
         pieces_in_one_wallet,
         start_date: start_date as u64,
         end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
     };
 
     let accounts = nft_shop::accounts::CreateMarket {
@@ -624,11 +640,9 @@ async fn failure_metadata_is_immutable() {
     context.banks_client.process_transaction(tx).await.unwrap();
 
     // SavePrimaryMetadataCreators
-    let (primary_metadata_creators, primary_metadata_creators_bump) =
-        find_primary_metadata_creators(&token.metadata);
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&token.metadata);
 
     let data = nft_shop::instruction::SavePrimaryMetadataCreators {
-        primary_metadata_creators_bump,
         creators: vec![nft_shop::state::Creator {
             address: store_admin.pubkey(),
             share: 100,
@@ -664,3 +678,163 @@ async fn failure_metadata_is_immutable() {
 
     assert_error(tx_error, ErrorCode::MetadataShouldBeMutable.into());
 }
+
+#[tokio::test]
+#[tokio::test]
+async fn failure_creators_shares_is_invalid() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let selling_resource_owner_keypair = Keypair::new();
+    airdrop(
+        &mut context,
+        &selling_resource_owner_keypair.pubkey(),
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let metadata_creators = Some(vec![Creator {
+        address: selling_resource_owner_keypair.pubkey(),
+        verified: false,
+        share: 100,
+    }]);
+    let max_supply = Some(1);
+    let metadata_is_mutable = true;
+
+    let (selling_resource_keypair, _, token) = init_selling_resource(
+        &mut context,
+        &store_admin,
+        &store_keypair,
+        &selling_resource_owner_keypair,
+        metadata_creators,
+        max_supply,
+        metadata_is_mutable,
+    )
+    .await;
+
+    let market_keypair = Keypair::new();
+
+    let treasury_mint_keypair = Keypair::new();
+    create_mint(
+        &mut context,
+        &treasury_mint_keypair,
+        &store_admin.pubkey(),
+        0,
+    )
+    .await;
+
+    let (treasury_owner, _) = find_treasury_owner_address(
+        &treasury_mint_keypair.pubkey(),
+        &selling_resource_keypair.pubkey(),
+        &market_keypair.pubkey(),
+    );
+
+    let treasury_holder_keypair = Keypair::new();
+    create_token_account(
+        &mut context,
+        &treasury_holder_keypair,
+        &treasury_mint_keypair.pubkey(),
+        &treasury_owner,
+    )
+    .await;
+
+    let start_date = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1;
+
+    let market_name = "1234_1234_".to_string();
+    let market_description = "1234_1234_1234_1234_".to_string();
+    let mutable = true;
+    let price = ONE_SOL;
+    let pieces_in_one_wallet = Some(1);
+
+    let data = nft_shop::instruction::CreateMarket {
+        name: market_name.to_owned(),
+        description: market_description.to_owned(),
+        mutable,
+        price,
+        pieces_in_one_wallet,
+        start_date: start_date as u64,
+        end_date: None,
+        pricing_curve: None,
+        allowlist: None,
+        referral_basis_points: 0,
+        allocation: None,
+        gate: None,
+        price_tiers: vec![],
+    };
+
+    let accounts = nft_shop::accounts::CreateMarket {
+        market: market_keypair.pubkey(),
+        store: store_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        treasury_mint: treasury_mint_keypair.pubkey(),
+        treasury_holder: treasury_holder_keypair.pubkey(),
+        treasury_owner,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &market_keypair,
+            &selling_resource_owner_keypair,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // SavePrimaryMetadataCreators
+    let (primary_metadata_creators, _) = find_primary_metadata_creators(&token.metadata);
+
+    let data = nft_shop::instruction::SavePrimaryMetadataCreators {
+        creators: vec![nft_shop::state::Creator {
+            address: store_admin.pubkey(),
+            share: 60,
+            verified: false,
+        }],
+    };
+
+    let accounts = nft_shop::accounts::SavePrimaryMetadataCreators {
+        metadata_update_authority: store_admin.pubkey(),
+        metadata: token.metadata,
+        primary_metadata_creators,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::CreatorsSharesIsInvalid.into());
+}
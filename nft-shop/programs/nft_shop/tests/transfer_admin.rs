@@ -0,0 +1,165 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use nft_shop::error::ErrorCode;
+use nft_shop::state::Store;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn transfer_admin_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let new_admin = Keypair::new();
+    airdrop(&mut context, &new_admin.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    // TransferAdmin
+
+    let data = nft_shop::instruction::TransferAdmin {};
+
+    let accounts = nft_shop::accounts::TransferAdmin {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        new_admin: new_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let store_account = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let store_account_data = Store::try_deserialize(&mut store_account.data.as_ref()).unwrap();
+
+    assert_eq!(store_admin.pubkey(), store_account_data.admin);
+    assert_eq!(Some(new_admin.pubkey()), store_account_data.pending_admin);
+
+    // AcceptAdmin
+
+    let data = nft_shop::instruction::AcceptAdmin {};
+
+    let accounts = nft_shop::accounts::AcceptAdmin {
+        store: store_keypair.pubkey(),
+        new_admin: new_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let store_account = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let store_account_data = Store::try_deserialize(&mut store_account.data.as_ref()).unwrap();
+
+    assert_eq!(new_admin.pubkey(), store_account_data.admin);
+    assert_eq!(None, store_account_data.pending_admin);
+}
+
+#[tokio::test]
+async fn failure_accept_admin_is_not_proposed() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let new_admin = Keypair::new();
+    airdrop(&mut context, &new_admin.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let fake_admin = Keypair::new();
+    airdrop(&mut context, &fake_admin.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    // TransferAdmin
+
+    let data = nft_shop::instruction::TransferAdmin {};
+
+    let accounts = nft_shop::accounts::TransferAdmin {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        new_admin: new_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // AcceptAdmin by not proposed wallet
+
+    let data = nft_shop::instruction::AcceptAdmin {};
+
+    let accounts = nft_shop::accounts::AcceptAdmin {
+        store: store_keypair.pubkey(),
+        new_admin: fake_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_admin],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::PendingAdminIsNotProposed.into());
+}
@@ -0,0 +1,238 @@
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use nft_shop::error::ErrorCode;
+use nft_shop::state::Store;
+use nft_shop::utils::{puffed_out_string, DESCRIPTION_MAX_LEN, NAME_MAX_LEN};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::{helpers::*, setup_functions::*};
+
+#[tokio::test]
+async fn update_store_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let name = String::from("123456789_123456789_");
+    let description = String::from("123456789_123456789_123456789_");
+
+    let data = nft_shop::instruction::UpdateStore {
+        new_name: Some(name.clone()),
+        new_description: Some(description.clone()),
+        new_platform_fee_basis_points: None,
+        new_platform_fee_destination: None,
+    };
+
+    let accounts = nft_shop::accounts::UpdateStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let store_account = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let store_account_data = Store::try_deserialize(&mut store_account.data.as_ref()).unwrap();
+
+    assert_eq!(store_admin.pubkey(), store_account_data.admin);
+    assert_eq!(
+        puffed_out_string(name, NAME_MAX_LEN),
+        store_account_data.name
+    );
+    assert_eq!(
+        puffed_out_string(description, DESCRIPTION_MAX_LEN),
+        store_account_data.description
+    );
+}
+
+#[tokio::test]
+async fn update_store_platform_fee_success() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let platform_fee_destination = Keypair::new();
+
+    let data = nft_shop::instruction::UpdateStore {
+        new_name: None,
+        new_description: None,
+        new_platform_fee_basis_points: Some(500),
+        new_platform_fee_destination: Some(platform_fee_destination.pubkey()),
+    };
+
+    let accounts = nft_shop::accounts::UpdateStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let store_account = context
+        .banks_client
+        .get_account(store_keypair.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let store_account_data = Store::try_deserialize(&mut store_account.data.as_ref()).unwrap();
+
+    assert_eq!(500, store_account_data.platform_fee_basis_points);
+    assert_eq!(
+        platform_fee_destination.pubkey(),
+        store_account_data.platform_fee_destination
+    );
+}
+
+#[tokio::test]
+async fn failure_update_store_name_is_too_long() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let name = String::from("123456789_123456789_123456789_123456789_1");
+
+    let data = nft_shop::instruction::UpdateStore {
+        new_name: Some(name),
+        new_description: None,
+        new_platform_fee_basis_points: None,
+        new_platform_fee_destination: None,
+    };
+
+    let accounts = nft_shop::accounts::UpdateStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::NameIsTooLong.into());
+}
+
+#[tokio::test]
+async fn failure_update_store_platform_fee_is_too_high() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (store_admin, store_keypair) = create_store(&mut context).await;
+
+    let data = nft_shop::instruction::UpdateStore {
+        new_name: None,
+        new_description: None,
+        new_platform_fee_basis_points: Some(10001),
+        new_platform_fee_destination: None,
+    };
+
+    let accounts = nft_shop::accounts::UpdateStore {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &store_admin],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ErrorCode::PlatformFeeIsTooHigh.into());
+}
+
+#[tokio::test]
+#[should_panic]
+async fn failure_update_store_signer_is_not_admin() {
+    let mut context = nft_shop_program_test().start_with_context().await;
+
+    let (_store_admin, store_keypair) = create_store(&mut context).await;
+
+    let fake_admin = Keypair::new();
+    airdrop(&mut context, &fake_admin.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let data = nft_shop::instruction::UpdateStore {
+        new_name: Some(String::from("123456789_123456789_")),
+        new_description: None,
+        new_platform_fee_basis_points: None,
+        new_platform_fee_destination: None,
+    };
+
+    let accounts = nft_shop::accounts::UpdateStore {
+        store: store_keypair.pubkey(),
+        store_admin: fake_admin.pubkey(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_admin],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
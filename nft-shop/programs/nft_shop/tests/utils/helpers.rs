@@ -4,8 +4,10 @@ use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction:
 use anchor_lang::{
     prelude::Pubkey,
     solana_program::{program_pack::Pack, system_instruction},
+    Event,
 };
 use solana_program_test::{BanksClientError, ProgramTestContext};
+use spl_token_2022::extension::{transfer_fee, ExtensionType};
 
 pub trait DirtyClone {
     fn dirty_clone(&self) -> Self;
@@ -127,3 +129,136 @@ pub async fn mint_to(
 
     context.banks_client.process_transaction(tx).await.unwrap();
 }
+
+pub async fn create_mint_with_transfer_fee(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    authority: &Pubkey,
+    decimals: u8,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::TransferFeeConfig,
+    ]);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token_2022::id(),
+            ),
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                Some(authority),
+                Some(authority),
+                transfer_fee_basis_points,
+                maximum_fee,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                authority,
+                Some(authority),
+                decimals,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn create_token_2022_account(
+    context: &mut ProgramTestContext,
+    account: &Keypair,
+    mint: &Pubkey,
+    manager: &Pubkey,
+) {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = ExtensionType::get_account_len::<spl_token_2022::state::Account>(&[
+        ExtensionType::TransferFeeAmount,
+    ]);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token_2022::id(),
+            ),
+            spl_token_2022::instruction::initialize_account(
+                &spl_token_2022::id(),
+                &account.pubkey(),
+                mint,
+                manager,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, account],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn mint_to_token_2022(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    to: &Pubkey,
+    owner: &Keypair,
+    amount: u64,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[spl_token_2022::instruction::mint_to(
+            &spl_token_2022::id(),
+            mint,
+            to,
+            &owner.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, owner],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+pub async fn process_transaction_with_logs(
+    context: &mut ProgramTestContext,
+    tx: Transaction,
+) -> Vec<String> {
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+
+    result.metadata.unwrap().log_messages
+}
+
+// Decodes events of type `T` emitted by `emit!` from transaction logs
+pub fn find_events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| base64::decode(data).ok())
+        .filter(|data| data.len() >= 8 && data[..8] == T::discriminator())
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
@@ -6,7 +6,7 @@ use anchor_client::solana_sdk::{
     transaction::{Transaction, TransactionError},
 };
 use anchor_lang::{
-    prelude::Pubkey,
+    prelude::{AccountMeta, Pubkey},
     solana_program::{
         instruction::{Instruction, InstructionError},
         system_program, sysvar,
@@ -23,7 +23,7 @@ use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
 
 use crate::utils::helpers::airdrop;
 
-use super::helpers::{create_token_account, DirtyClone};
+use super::helpers::{create_mint, create_token_account, mint_to, DirtyClone};
 
 pub const ONE_SOL: u64 = 1_000_000_000;
 
@@ -180,6 +180,33 @@ pub async fn create_nft(
     })
 }
 
+// Sets `collection` as verified collection of `nft`, both must have the same owner
+pub async fn verify_collection(
+    context: &mut ProgramTestContext,
+    nft: &NFT,
+    collection: &NFT,
+) -> Result<(), BanksClientError> {
+    let ix = mpl_token_metadata::instruction::set_and_verify_collection(
+        token_metadata_program_id(),
+        nft.metadata,
+        collection.owner.pubkey(),
+        collection.owner.pubkey(),
+        nft.owner.pubkey(),
+        collection.mint.pubkey(),
+        collection.metadata,
+        collection.master_edition.edition,
+        None,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&collection.owner.pubkey()),
+        &[&collection.owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
 pub async fn init_selling_resource(
     context: &mut ProgramTestContext,
     store_admin: &Keypair,
@@ -200,17 +227,12 @@ pub async fn init_selling_resource(
     .await
     .unwrap();
 
-    let (vault_owner, vault_owner_bump) =
-        find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
 
     let vault = Keypair::new();
     create_token_account(context, &vault, &token.mint.pubkey(), &vault_owner).await;
 
-    let data = nft_shop::instruction::InitSellingResource {
-        master_edition_bump: token.master_edition.bump,
-        vault_owner_bump,
-        max_supply,
-    };
+    let data = nft_shop::instruction::InitSellingResource { max_supply };
 
     let selling_resource_keypair = Keypair::new();
 
@@ -246,3 +268,177 @@ pub async fn init_selling_resource(
 
     (selling_resource_keypair, vault, token)
 }
+
+#[derive(Debug)]
+pub struct FungibleToken {
+    pub mint: Keypair,    // Resource Mint
+    pub owner: Keypair,   // Resource Owner
+    pub token: Pubkey,    // Resource Token
+    pub metadata: Pubkey, // Metaplex Metadata
+}
+
+pub async fn create_fungible_token(
+    context: &mut ProgramTestContext,
+    owner: Keypair,
+    metadata_creators: Option<Vec<Creator>>,
+    amount: u64,
+) -> Result<FungibleToken, BanksClientError> {
+    let mint = Keypair::new();
+
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+
+    // CreateToken
+    let create_token_ix = Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::CreateToken {
+            name: "Solana Course Token".to_string(),
+            symbol: "SOLC".to_string(),
+            uri: "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+                .to_string(),
+            creators: metadata_creators,
+            seller_fee_basis_points: 10,
+            is_mutable: true,
+        }
+        .data(),
+        accounts: nft_minter::accounts::CreateToken {
+            payer: owner.pubkey(),
+            mint_account: mint.pubkey(),
+            mint_authority: owner.pubkey(),
+            update_authority: owner.pubkey(),
+            metadata_account: metadata,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_token_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    let token = Keypair::new();
+    create_token_account(context, &token, &mint.pubkey(), &owner.pubkey()).await;
+    mint_to(context, &mint.pubkey(), &token.pubkey(), &owner, amount).await;
+
+    Ok(FungibleToken {
+        mint,
+        owner,
+        token: token.pubkey(),
+        metadata,
+    })
+}
+
+pub async fn init_fungible_selling_resource(
+    context: &mut ProgramTestContext,
+    store_admin: &Keypair,
+    store_keypair: &Keypair,
+    selling_resource_owner_keypair: &Keypair,
+    creators: Option<Vec<Creator>>,
+    amount: u64,
+) -> (Keypair, Keypair, FungibleToken) {
+    // Create Token to be provided as a resource for sale
+    let token = create_fungible_token(context, store_admin.dirty_clone(), creators, amount)
+        .await
+        .unwrap();
+
+    let (vault_owner, _) = find_vault_owner_address(&token.mint.pubkey(), &store_keypair.pubkey());
+
+    let vault = Keypair::new();
+    create_token_account(context, &vault, &token.mint.pubkey(), &vault_owner).await;
+
+    let data = nft_shop::instruction::InitFungibleSellingResource {
+        _vault_owner_bump: vault_owner_bump,
+        amount,
+    };
+
+    let selling_resource_keypair = Keypair::new();
+
+    let accounts = nft_shop::accounts::InitFungibleSellingResource {
+        store: store_keypair.pubkey(),
+        store_admin: store_admin.pubkey(),
+        selling_resource: selling_resource_keypair.pubkey(),
+        selling_resource_owner: selling_resource_owner_keypair.pubkey(),
+        resource_mint: token.mint.pubkey(),
+        resource_token: token.token,
+        metadata: token.metadata,
+        vault: vault.pubkey(),
+        vault_owner,
+        rent: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: nft_shop::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&store_admin.pubkey()),
+        &[&store_admin, &selling_resource_keypair],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (selling_resource_keypair, vault, token)
+}
+
+// Creates new mint and token account of `user_wallet` to receive `edition` of `resource_mint`
+// and returns accounts in the order expected by `buy_many`
+pub async fn create_edition_accounts(
+    context: &mut ProgramTestContext,
+    user_wallet: &Keypair,
+    resource_mint: &Pubkey,
+    edition: u64,
+) -> Vec<AccountMeta> {
+    let new_mint_keypair = Keypair::new();
+    create_mint(context, &new_mint_keypair, &user_wallet.pubkey(), 0).await;
+
+    let new_token_account = Keypair::new();
+    create_token_account(
+        context,
+        &new_token_account,
+        &new_mint_keypair.pubkey(),
+        &user_wallet.pubkey(),
+    )
+    .await;
+    mint_to(
+        context,
+        &new_mint_keypair.pubkey(),
+        &new_token_account.pubkey(),
+        user_wallet,
+        1,
+    )
+    .await;
+
+    let (new_metadata, _) = find_metadata_account(&new_mint_keypair.pubkey());
+    let (new_edition, _) = find_master_edition_account(&new_mint_keypair.pubkey());
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            resource_mint.as_ref(),
+            mpl_token_metadata::state::EDITION.as_bytes(),
+            (edition / mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE)
+                .to_string()
+                .as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+
+    vec![
+        AccountMeta::new(new_metadata, false),
+        AccountMeta::new(new_edition, false),
+        AccountMeta::new(new_mint_keypair.pubkey(), false),
+        AccountMeta::new_readonly(new_token_account.pubkey(), false),
+        AccountMeta::new(edition_marker, false),
+    ]
+}
@@ -125,7 +125,7 @@ export const initSellingResource = async ({
     console.log(error);
   }
 
-  const [masterEdition, masterEditionBump] = findMasterEditionAddress({
+  const [masterEdition] = findMasterEditionAddress({
     mint: resourceMintKeypair.publicKey,
   });
 
@@ -157,7 +157,7 @@ export const initSellingResource = async ({
     console.log(error);
   }
 
-  const [vaultOwner, vaultOwnerBump] = findVaultOwnerAddress(
+  const [vaultOwner] = findVaultOwnerAddress(
     resourceMintKeypair.publicKey,
     storeKeypair.publicKey
   );
@@ -174,7 +174,7 @@ export const initSellingResource = async ({
   // Init Selling Resource
   try {
     const tx = await nftShopProgram.methods
-      .initSellingResource(masterEditionBump, vaultOwnerBump, new BN(maxSupply))
+      .initSellingResource(new BN(maxSupply))
       .accounts({
         store: storeKeypair.publicKey,
         storeAdmin: storeAdminKeypair.publicKey,
@@ -243,7 +243,7 @@ describe("nft_shop", async () => {
       mint: sellingResourceData.resource,
     });
 
-    const [primaryMetadataCreators, primaryMetadataCreatorsBump] =
+    const [primaryMetadataCreators] =
       findPrimaryMetadataCreatorsAddress(metadata);
 
     const primaryRoyaltiesHolder = anchor.web3.Keypair.generate();
@@ -258,7 +258,7 @@ describe("nft_shop", async () => {
     // Save Primary Metadata Creators
     try {
       const tx = await nftShopProgram.methods
-        .savePrimaryMetadataCreators(primaryMetadataCreatorsBump, creators)
+        .savePrimaryMetadataCreators(creators)
         .accounts({
           metadataUpdateAuthority: sellingResourceOwnerKeypair.publicKey,
           metadata,
@@ -280,9 +280,10 @@ describe("nft_shop", async () => {
       mint: treasuryMintKeypair,
     });
 
-    const [treasuryOwner, treasuryOwnerBump] = findTreasuryOwnerAddress(
+    const [treasuryOwner] = findTreasuryOwnerAddress(
       treasuryMintKeypair.publicKey,
-      sellingResourceKeypair.publicKey
+      sellingResourceKeypair.publicKey,
+      marketKeypair.publicKey
     );
 
     const treasuryHolderKeypair = anchor.web3.Keypair.generate();
@@ -301,19 +302,30 @@ describe("nft_shop", async () => {
     const piecesInOneWallet = new BN(1);
     const startDate = new BN(Math.round(Date.now() / 1000));
     const endDate = null;
+    const pricingCurve = null;
+    const allowlist = null;
+    const referralBasisPoints = 0;
+    const allocation = null;
+    const gate = null;
+    const priceTiers = [];
 
     // Create Market
     try {
       const tx = await nftShopProgram.methods
         .createMarket(
-          treasuryOwnerBump,
           marketName,
           marketDescription,
           mutable,
           price,
           piecesInOneWallet,
           startDate,
-          endDate
+          endDate,
+          pricingCurve,
+          allowlist,
+          referralBasisPoints,
+          allocation,
+          gate,
+          priceTiers
         )
         .accounts({
           market: marketKeypair.publicKey,
@@ -345,11 +357,20 @@ describe("nft_shop", async () => {
           newMarketDescription,
           newMutable,
           newPrice,
-          newPiecesInOneWallet
+          newPiecesInOneWallet,
+          null,
+          null,
+          null
         )
         .accounts({
           market: marketKeypair.publicKey,
           sellingResourceOwner: sellingResourceOwnerKeypair.publicKey,
+          treasuryMint: null,
+          treasuryHolder: null,
+          treasuryOwner: null,
+          oldTreasuryHolder: null,
+          oldTreasuryOwner: null,
+          oldTreasuryTokenProgram: null,
         })
         .signers([sellingResourceOwnerKeypair])
         .rpc();
@@ -358,12 +379,12 @@ describe("nft_shop", async () => {
       console.log(error);
     }
 
-    const [tradeHistory, tradeHistoryBump] = findTradeHistoryAddress(
+    const [tradeHistory] = findTradeHistoryAddress(
       payer.publicKey,
       marketKeypair.publicKey
     );
 
-    const [vaultOwner, vaultOwnerBump] = findVaultOwnerAddress(
+    const [vaultOwner] = findVaultOwnerAddress(
       sellingResourceData.resource,
       sellingResourceData.store
     );
@@ -421,7 +442,7 @@ describe("nft_shop", async () => {
     // Buy
     try {
       const tx = await nftShopProgram.methods
-        .buy(tradeHistoryBump, vaultOwnerBump)
+        .buy(null)
         .accounts({
           market: marketKeypair.publicKey,
           sellingResource: sellingResourceKeypair.publicKey,
@@ -439,6 +460,13 @@ describe("nft_shop", async () => {
           newTokenAccount: newTokenAccountMint.publicKey,
           metadata,
           tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          referral: null,
+          treasuryMint: null,
+          treasuryTokenProgram: null,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          gateUsage: null,
+          primaryMetadataCreators,
         })
         .signers([userWalletKeypair])
         .rpc();
@@ -462,7 +490,7 @@ describe("nft_shop", async () => {
       console.log(error);
     }
 
-    const [payoutTicket, payoutTicketBump] = findPayoutTicketAddress(
+    const [payoutTicket] = findPayoutTicketAddress(
       marketKeypair.publicKey,
       primaryRoyaltiesHolder.publicKey
     );
@@ -486,7 +514,7 @@ describe("nft_shop", async () => {
     // Withdraw
     try {
       const tx = await nftShopProgram.methods
-        .withdraw(treasuryOwnerBump, payoutTicketBump)
+        .withdraw()
         .accounts({
           market: marketKeypair.publicKey,
           sellingResource: sellingResourceKeypair.publicKey,
@@ -518,9 +546,10 @@ describe("nft_shop", async () => {
     // Claim Resource
     try {
       const tx = await nftShopProgram.methods
-        .claimResource(vaultOwnerBump)
+        .claimResource()
         .accounts({
           market: marketKeypair.publicKey,
+          store: storeKeypair.publicKey,
           treasuryHolder: treasuryHolderKeypair.publicKey,
           sellingResource: sellingResourceKeypair.publicKey,
           sellingResourceOwner: sellingResourceOwnerKeypair.publicKey,
@@ -530,6 +559,7 @@ describe("nft_shop", async () => {
           destination: claimTokenAccount.publicKey,
           tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         })
+        .remainingAccounts(primaryMetadataCreatorsData)
         .signers([sellingResourceOwnerKeypair])
         .rpc();
       console.log("Transaction [Claim Resource]", tx);
@@ -162,13 +162,15 @@ export const findVaultOwnerAddress = (
 
 export const findTreasuryOwnerAddress = (
   treasuryMint: anchor.web3.PublicKey,
-  sellingResource: anchor.web3.PublicKey
+  sellingResource: anchor.web3.PublicKey,
+  market: anchor.web3.PublicKey
 ): [anchor.web3.PublicKey, number] =>
   anchor.web3.PublicKey.findProgramAddressSync(
     [
       Buffer.from(HOLDER_PREFIX),
       treasuryMint.toBuffer(),
       sellingResource.toBuffer(),
+      market.toBuffer(),
     ],
     PROGRAM_ID
   );